@@ -0,0 +1,93 @@
+//! Generate the implementations of inter-struct in the build script of a crate.
+//!
+//! Instead of reading files in every single derive macro, all structs of the library are
+//! processed at once by the build script. The implementations are written to a file in
+//! `OUT_DIR`, which is then included by the library:
+//!
+//! `build.rs`
+//! ```rust, ignore
+//! fn main() {
+//!     inter_struct_build::Builder::new().generate().unwrap();
+//! }
+//! ```
+//!
+//! `lib.rs`
+//! ```rust, ignore
+//! include!(concat!(env!("OUT_DIR"), "/inter_struct.rs"));
+//! ```
+//!
+//! The derives and attributes on the structs stay the same. While the library is compiled, the
+//! derive macros don't generate any code. Binaries and tests of the package aren't covered by the
+//! build script, which is why their derive macros keep working as usual.
+use std::io;
+use std::path::PathBuf;
+
+use inter_struct_core::BUILD_CRATE_VARIABLE;
+
+/// Configures and runs the code generation.
+pub struct Builder {
+    /// The name of the generated file in `OUT_DIR`.
+    output: String,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder {
+            output: "inter_struct.rs".to_string(),
+        }
+    }
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Set the name of the generated file in `OUT_DIR`. Defaults to `inter_struct.rs`.
+    pub fn output(mut self, name: impl Into<String>) -> Builder {
+        self.output = name.into();
+        self
+    }
+
+    /// Generate the implementations and write them to `OUT_DIR`.
+    ///
+    /// Cargo is told to rerun the build script as soon as one of the files, that have been read
+    /// along the way, changes.
+    /// Errors in the attributes of structs don't fail the build script. They're written to the
+    /// generated file instead, so the compiler reports them.
+    ///
+    /// Returns the path of the generated file.
+    pub fn generate(self) -> io::Result<PathBuf> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "OUT_DIR isn't set, inter-struct-build has to be used in a build script.",
+            )
+        })?;
+
+        let expansion = inter_struct_core::expand_crate();
+
+        let path = PathBuf::from(out_dir).join(&self.output);
+        std::fs::write(&path, expansion.tokens.to_string())?;
+
+        // Disable the derive macros, while the library is compiled.
+        if let Some(crate_name) = &expansion.crate_name {
+            println!("cargo:rustc-env={BUILD_CRATE_VARIABLE}={crate_name}");
+        }
+
+        for file in &expansion.files {
+            // Cargo reruns the build script on every build, if it's given a file that doesn't
+            // exist. Its directory is watched instead, so the creation of the file is noticed.
+            let watched = match file.exists() {
+                true => file.as_path(),
+                false => match file.parent() {
+                    Some(parent) if parent.exists() => parent,
+                    _ => continue,
+                },
+            };
+            println!("cargo:rerun-if-changed={}", watched.display());
+        }
+
+        Ok(path)
+    }
+}
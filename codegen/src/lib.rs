@@ -1,12 +1,4 @@
-mod error;
-mod generate;
-mod helper;
-mod module;
-mod parse;
-
-#[cfg(feature = "debug")]
-mod debug;
-
+use inter_struct_core::Mode;
 use proc_macro::TokenStream;
 
 /// Implement the `Into` trait on this struct.
@@ -25,9 +17,9 @@ use proc_macro::TokenStream;
 ///     pub test: String,
 /// }
 /// ```
-#[proc_macro_derive(StructInto, attributes(struct_into))]
+#[proc_macro_derive(StructInto, attributes(struct_into, inter_struct))]
 pub fn struct_into(struct_ast: TokenStream) -> TokenStream {
-    generate::into::struct_into_inner(struct_ast)
+    inter_struct_core::derive(Mode::Into, struct_ast.into()).into()
 }
 
 /// Implement the `Into` trait on this struct with `Default::default` for missing fields.
@@ -47,9 +39,9 @@ pub fn struct_into(struct_ast: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
-#[proc_macro_derive(StructIntoDefault, attributes(struct_into_default))]
+#[proc_macro_derive(StructIntoDefault, attributes(struct_into_default, inter_struct))]
 pub fn struct_into_default(struct_ast: TokenStream) -> TokenStream {
-    generate::into::struct_into_default_inner(struct_ast)
+    inter_struct_core::derive(Mode::IntoDefault, struct_ast.into()).into()
 }
 
 /// Implement the `StructMerge` trait on this struct.
@@ -68,9 +60,9 @@ pub fn struct_into_default(struct_ast: TokenStream) -> TokenStream {
 ///     pub test: String,
 /// }
 /// ```
-#[proc_macro_derive(StructMerge, attributes(struct_merge))]
+#[proc_macro_derive(StructMerge, attributes(struct_merge, inter_struct))]
 pub fn struct_merge(struct_ast: TokenStream) -> TokenStream {
-    generate::merge::struct_merge_inner(struct_ast)
+    inter_struct_core::derive(Mode::Merge, struct_ast.into()).into()
 }
 
 /// Implement the `StructMergeRef` trait on this struct.
@@ -89,7 +81,41 @@ pub fn struct_merge(struct_ast: TokenStream) -> TokenStream {
 ///     pub test: String,
 /// }
 /// ```
-#[proc_macro_derive(StructMergeRef, attributes(struct_merge_ref))]
+#[proc_macro_derive(StructMergeRef, attributes(struct_merge_ref, inter_struct))]
 pub fn struct_merge_ref(struct_ast: TokenStream) -> TokenStream {
-    generate::merge::struct_merge_ref_inner(struct_ast)
+    inter_struct_core::derive(Mode::MergeRef, struct_ast.into()).into()
+}
+
+/// Mark this struct as a target of the other derive macros in callback mode.
+///
+/// This emits a hidden macro next to the struct, which describes its fields.
+/// Source structs then reference the target via `callback = "..."`, which expands into a call to
+/// that macro. No files are read, which is why this works for structs in other crates, structs
+/// that are generated by other macros and structs in tests.
+///
+/// `structs.rs`
+/// ```rust, ignore
+/// use inter_struct::prelude::*;
+///
+/// #[derive(InterStructTarget)]
+/// pub struct Target {
+///     pub test: String,
+/// }
+///
+/// #[derive(StructInto)]
+/// #[struct_into(callback = "crate::structs::Target")]
+/// pub struct Test {
+///     pub test: String,
+/// }
+/// ```
+#[proc_macro_derive(InterStructTarget)]
+pub fn inter_struct_target(struct_ast: TokenStream) -> TokenStream {
+    inter_struct_core::inter_struct_target(struct_ast.into()).into()
+}
+
+/// Generate an implementation in callback mode. This is called by the macros of target structs.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __inter_struct_callback(input: TokenStream) -> TokenStream {
+    inter_struct_core::callback(input.into()).into()
 }
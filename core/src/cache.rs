@@ -0,0 +1,384 @@
+//! Process-wide caches, that are shared by all derives.
+//!
+//! Each derive and each of its targets needs to read and parse the files of the crate.
+//! As the same files are used over and over again, the parsed files and the results of whole
+//! resolutions are cached.
+//!
+//! Every cached value remembers the state (modification time and size) of the files it has been
+//! created from. Values are only reused as long as none of these files changed, which matters for
+//! long-running processes such as the proc macro servers of IDEs.
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::error::*;
+use crate::module::ModuleItem;
+
+/// The state of a file at the time it has been read.
+#[derive(Clone, PartialEq, Eq)]
+struct FileStamp {
+    path: PathBuf,
+    /// The modification time and size of the file or `None`, if it doesn't exist.
+    state: Option<(Option<SystemTime>, u64)>,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> FileStamp {
+        let state = std::fs::metadata(path)
+            .ok()
+            .map(|metadata| (metadata.modified().ok(), metadata.len()));
+
+        FileStamp {
+            path: path.to_path_buf(),
+            state,
+        }
+    }
+
+    /// Whether the file is still in the same state.
+    fn is_current(&self) -> bool {
+        FileStamp::read(&self.path) == *self
+    }
+}
+
+struct CachedFile {
+    stamp: FileStamp,
+    items: Arc<Vec<ModuleItem>>,
+}
+
+/// The result of a resolution, i.e. the source code of a struct.
+#[derive(Clone)]
+pub struct Resolved {
+    /// The path to the struct, as it's used in the generated code.
+    pub path: String,
+    /// The source code of the struct.
+    pub source: String,
+}
+
+/// Everything a resolution depends on, besides the files it reads.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct Query {
+    /// The root file of the crate that's being compiled.
+    pub crate_root: PathBuf,
+    /// The file and name of the source struct, if the target is resolved relative to it.
+    pub caller: Option<(PathBuf, String)>,
+    /// The target, as it has been specified in the attribute.
+    pub target: String,
+}
+
+struct CachedResolution {
+    /// All files that have been read during the resolution.
+    files: Vec<FileStamp>,
+    resolved: Resolved,
+}
+
+/// All parsed files by their path.
+static FILES: Mutex<BTreeMap<PathBuf, CachedFile>> = Mutex::new(BTreeMap::new());
+
+/// All successful resolutions. The key also contains the forwarded cfg environment, as it
+/// decides which items are active.
+static RESOLUTIONS: Mutex<BTreeMap<(Query, String), CachedResolution>> =
+    Mutex::new(BTreeMap::new());
+
+thread_local! {
+    /// The files that have been read by the resolution that's currently running on this thread.
+    static READ_FILES: RefCell<Option<Vec<FileStamp>>> = const { RefCell::new(None) };
+}
+
+/// Read and parse the given file, unless it's already cached.
+pub fn parse_file(path: &Path, span: Span) -> Result<Arc<Vec<ModuleItem>>, TokenStream> {
+    let stamp = recorded_stamp(path);
+
+    if let Some(cached) = lock(&FILES).get(path) {
+        if cached.stamp == stamp {
+            return Ok(cached.items.clone());
+        }
+    }
+
+    let file_content = ok_or_err_return!(
+        std::fs::read_to_string(path),
+        span,
+        "Failed to open file {:?}: {:?}",
+        path
+    );
+
+    let file_ast = ok_or_err_return!(
+        syn::parse_file(&file_content),
+        span,
+        "Failed to parse file {:?}: {:?}",
+        path
+    );
+
+    let items = Arc::new(ModuleItem::summarize(file_ast.items));
+    lock(&FILES).insert(
+        path.to_path_buf(),
+        CachedFile {
+            stamp,
+            items: items.clone(),
+        },
+    );
+
+    Ok(items)
+}
+
+/// Check whether the given file exists.
+///
+/// The check is recorded, so cached resolutions are invalidated if the file is created later on.
+pub fn exists(path: &Path) -> bool {
+    recorded_stamp(path).state.is_some()
+}
+
+/// Run a resolution, unless the same query has already been resolved and none of the files it
+/// has read changed since.
+///
+/// Failed resolutions aren't cached, so their errors are always up to date.
+pub fn resolve(
+    query: Query,
+    resolve: impl FnOnce() -> Result<Resolved, TokenStream>,
+) -> Result<Resolved, TokenStream> {
+    let key = (query, environment());
+    if let Some(cached) = lock(&RESOLUTIONS).get(&key) {
+        if cached.files.iter().all(FileStamp::is_current) {
+            extend_recording(&cached.files);
+            return Ok(cached.resolved.clone());
+        }
+    }
+
+    let (result, files) = recording(resolve);
+
+    if let Ok(resolved) = &result {
+        lock(&RESOLUTIONS).insert(
+            key,
+            CachedResolution {
+                files,
+                resolved: resolved.clone(),
+            },
+        );
+    }
+
+    result
+}
+
+/// Run `f` and return the paths of all files it depends on.
+///
+/// This includes the files of cached resolutions and files that don't exist (yet).
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Vec<PathBuf>) {
+    let (result, files) = recording(f);
+
+    let mut paths: Vec<PathBuf> = files.into_iter().map(|stamp| stamp.path).collect();
+    paths.sort();
+    paths.dedup();
+
+    (result, paths)
+}
+
+/// Run `f` while recording all files it reads.
+///
+/// The files are also added to the recording that's currently running, if there's any.
+fn recording<T>(f: impl FnOnce() -> T) -> (T, Vec<FileStamp>) {
+    let outer = READ_FILES.with(|files| files.replace(Some(Vec::new())));
+    let result = f();
+    let files = READ_FILES
+        .with(|files| files.replace(outer))
+        .unwrap_or_default();
+    extend_recording(&files);
+
+    (result, files)
+}
+
+/// Add the given files to the recording that's currently running.
+fn extend_recording(stamps: &[FileStamp]) {
+    READ_FILES.with(|files| {
+        if let Some(files) = files.borrow_mut().as_mut() {
+            files.extend_from_slice(stamps);
+        }
+    });
+}
+
+/// Record the current state of a file for the resolution that's currently running.
+///
+/// This is necessary for all files that influence a resolution, but aren't parsed via
+/// [parse_file], i.e. manifests.
+pub fn record(path: &Path) {
+    recorded_stamp(path);
+}
+
+fn recorded_stamp(path: &Path) -> FileStamp {
+    let stamp = FileStamp::read(path);
+    READ_FILES.with(|files| {
+        if let Some(files) = files.borrow_mut().as_mut() {
+            files.push(stamp.clone());
+        }
+    });
+
+    stamp
+}
+
+/// All forwarded features and cfg options, which influence the evaluation of `#[cfg]` attributes.
+fn environment() -> String {
+    let mut variables: Vec<String> = std::env::vars()
+        .filter(|(key, _)| key.starts_with("CARGO_FEATURE_") || key.starts_with("CARGO_CFG_"))
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    variables.sort();
+
+    variables.join("\n")
+}
+
+/// Lock one of the caches.
+///
+/// A panic while holding the lock doesn't leave the cache in an inconsistent state, as values are
+/// only ever inserted as a whole.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    /// The tests change files and environment variables, which are shared by all tests.
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    /// Create an empty directory for a test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "inter-struct-cache-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    /// Write a file and set its modification time.
+    fn write(path: &Path, content: &str, modified: SystemTime) {
+        std::fs::write(path, content).unwrap();
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn struct_names(path: &Path) -> Vec<String> {
+        parse_file(path, Span::call_site())
+            .unwrap()
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Struct { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn query(target: &str) -> Query {
+        Query {
+            crate_root: PathBuf::from("src/lib.rs"),
+            caller: None,
+            target: target.to_string(),
+        }
+    }
+
+    /// Run a resolution, that reads the given file and counts how often it actually runs.
+    fn resolve_file(target: &str, path: &Path, runs: &Cell<usize>) -> String {
+        let resolved = resolve(query(target), || {
+            runs.set(runs.get() + 1);
+            let names = match exists(path) {
+                true => struct_names(path).join(","),
+                false => String::new(),
+            };
+
+            Ok(Resolved {
+                path: target.to_string(),
+                source: names,
+            })
+        });
+
+        resolved.unwrap().source
+    }
+
+    #[test]
+    fn parse_file_is_invalidated() {
+        let _serial = lock(&SERIAL);
+        let file = temp_dir("parse").join("lib.rs");
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        write(&file, "struct A;", time);
+        assert_eq!(struct_names(&file), vec!["A"]);
+
+        // Same size and modification time, the cached file is used.
+        write(&file, "struct B;", time);
+        assert_eq!(struct_names(&file), vec!["A"]);
+
+        // Only the modification time changed.
+        write(&file, "struct C;", time + Duration::from_secs(1));
+        assert_eq!(struct_names(&file), vec!["C"]);
+
+        // Only the size changed.
+        write(&file, "struct Da;", time + Duration::from_secs(1));
+        assert_eq!(struct_names(&file), vec!["Da"]);
+    }
+
+    #[test]
+    fn resolve_is_invalidated() {
+        let _serial = lock(&SERIAL);
+        let file = temp_dir("resolve").join("lib.rs");
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let runs = Cell::new(0);
+
+        write(&file, "struct A;", time);
+        assert_eq!(resolve_file("changed", &file, &runs), "A");
+        assert_eq!(resolve_file("changed", &file, &runs), "A");
+        assert_eq!(runs.get(), 1);
+
+        write(&file, "struct A; struct B;", time + Duration::from_secs(1));
+        assert_eq!(resolve_file("changed", &file, &runs), "A,B");
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn resolve_is_invalidated_by_created_file() {
+        let _serial = lock(&SERIAL);
+        let file = temp_dir("created").join("lib.rs");
+        let runs = Cell::new(0);
+
+        assert_eq!(resolve_file("created", &file, &runs), "");
+        assert_eq!(resolve_file("created", &file, &runs), "");
+        assert_eq!(runs.get(), 1);
+
+        write(&file, "struct A;", SystemTime::now());
+        assert_eq!(resolve_file("created", &file, &runs), "A");
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn resolve_depends_on_cfg_environment() {
+        let _serial = lock(&SERIAL);
+        let file = temp_dir("environment").join("lib.rs");
+        let runs = Cell::new(0);
+        write(&file, "struct A;", SystemTime::now());
+
+        std::env::remove_var("CARGO_CFG_INTER_STRUCT_CACHE_TEST");
+        resolve_file("environment", &file, &runs);
+        resolve_file("environment", &file, &runs);
+        assert_eq!(runs.get(), 1);
+
+        std::env::set_var("CARGO_CFG_INTER_STRUCT_CACHE_TEST", "1");
+        resolve_file("environment", &file, &runs);
+        std::env::remove_var("CARGO_CFG_INTER_STRUCT_CACHE_TEST");
+        assert_eq!(runs.get(), 2);
+    }
+}
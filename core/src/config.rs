@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use syn::Type;
+use toml::{Table, Value};
+
+use crate::cache;
+use crate::error::*;
+use crate::manifest::read_toml;
+
+/// The name of the optional configuration file next to the `Cargo.toml` of a package.
+pub const CONFIG_FILE: &str = "inter-struct.toml";
+
+/// The settings of the optional `inter-struct.toml` of a package.
+///
+/// They apply to all derives of the package. Without the file, all settings are empty and
+/// everything behaves as if it didn't exist.
+///
+/// ```toml
+/// # The root file of the library, relative to the package.
+/// root = "generated/lib.rs"
+///
+/// # Types that are treated as equal, when fields are compared.
+/// [type-aliases]
+/// AlsoString = "String"
+///
+/// # The package directories of crates, which aren't found via the manifest.
+/// [crates]
+/// other_crate = "../other"
+///
+/// [defaults]
+/// # The features that are assumed to be enabled, if they aren't forwarded by a build script.
+/// # This is the only default, other keys are rejected.
+/// features = ["postgres"]
+/// ```
+#[derive(Default)]
+pub struct Config {
+    /// The root file of the library, which replaces the one of the manifest.
+    pub root: Option<PathBuf>,
+    /// Type aliases by their name.
+    pub type_aliases: BTreeMap<String, Type>,
+    /// The package directories of crates by their crate name.
+    pub crates: BTreeMap<String, PathBuf>,
+    /// The enabled features, if they aren't forwarded via `CARGO_FEATURE_*` variables.
+    pub default_features: Option<Vec<String>>,
+}
+
+impl Config {
+    /// Read the `inter-struct.toml` in the given package directory, if there is one.
+    pub fn read(dir: &Path, span: Span) -> Result<Config, TokenStream> {
+        let path = dir.join(CONFIG_FILE);
+        if !cache::exists(&path) {
+            return Ok(Config::default());
+        }
+
+        let table = read_toml(&path, "configuration", span)?;
+        let mut config = Config::default();
+        for (key, value) in table.iter() {
+            match key.as_str() {
+                "root" => config.root = Some(dir.join(string(&path, key, value, span)?)),
+                "type-aliases" => {
+                    for (name, ty) in self::table(&path, key, value, span)? {
+                        let ty = string(&path, &format!("{key}.{name}"), ty, span)?;
+                        let ty: Type = ok_or_err_return!(
+                            syn::parse_str(ty),
+                            span,
+                            "Failed to parse type alias '{}' in {:?}: {}",
+                            name,
+                            path
+                        );
+                        config.type_aliases.insert(name.clone(), ty);
+                    }
+                }
+                "crates" => {
+                    for (name, dir_value) in self::table(&path, key, value, span)? {
+                        let crate_dir = string(&path, &format!("{key}.{name}"), dir_value, span)?;
+                        config.crates.insert(name.clone(), dir.join(crate_dir));
+                    }
+                }
+                "defaults" => {
+                    for (name, option) in self::table(&path, key, value, span)? {
+                        let key = format!("{key}.{name}");
+                        match name.as_str() {
+                            "features" => {
+                                config.default_features = Some(strings(&path, &key, option, span)?)
+                            }
+                            _ => return Err(unknown_key(&path, &key, span)),
+                        }
+                    }
+                }
+                _ => return Err(unknown_key(&path, key, span)),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn string<'a>(
+    path: &Path,
+    key: &str,
+    value: &'a Value,
+    span: Span,
+) -> Result<&'a str, TokenStream> {
+    match value.as_str() {
+        Some(value) => Ok(value),
+        None => Err(err!(span, "'{}' in {:?} has to be a string.", key, path)),
+    }
+}
+
+fn strings(path: &Path, key: &str, value: &Value, span: Span) -> Result<Vec<String>, TokenStream> {
+    let values: Option<Vec<String>> = value.as_array().and_then(|values| {
+        values
+            .iter()
+            .map(|value| value.as_str().map(str::to_string))
+            .collect()
+    });
+
+    match values {
+        Some(values) => Ok(values),
+        None => Err(err!(
+            span,
+            "'{}' in {:?} has to be an array of strings.",
+            key,
+            path
+        )),
+    }
+}
+
+fn table<'a>(
+    path: &Path,
+    key: &str,
+    value: &'a Value,
+    span: Span,
+) -> Result<&'a Table, TokenStream> {
+    match value.as_table() {
+        Some(table) => Ok(table),
+        None => Err(err!(span, "'{}' in {:?} has to be a table.", key, path)),
+    }
+}
+
+fn unknown_key(path: &Path, key: &str, span: Span) -> TokenStream {
+    err!(span, "Unknown key '{}' in {:?}.", key, path)
+}
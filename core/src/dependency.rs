@@ -0,0 +1,315 @@
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use proc_macro2::{Span, TokenStream};
+use toml::{Table, Value};
+
+use crate::cache;
+use crate::config::Config;
+use crate::error::*;
+use crate::manifest::{existing, is_same_name, read_toml, Manifest};
+
+/// The workspace of the crate that's currently being compiled.
+///
+/// Packages that aren't part of a workspace are treated as their own workspace.
+pub struct Workspace {
+    /// The root manifest of the workspace.
+    pub manifest: Manifest,
+    /// The parsed `Cargo.lock`, which pins the versions of all registry dependencies.
+    lock: Option<Table>,
+}
+
+impl Workspace {
+    /// Find the workspace of the given package and read its `Cargo.lock`.
+    pub fn of(manifest: &Manifest, span: Span) -> Result<Workspace, TokenStream> {
+        let manifest = match manifest.workspace(span)? {
+            Some(workspace) => workspace,
+            None => manifest.clone(),
+        };
+
+        let lock = match existing(manifest.dir.join("Cargo.lock")) {
+            Some(lock_path) => Some(read_toml(&lock_path, "lock file", span)?),
+            None => None,
+        };
+
+        Ok(Workspace { manifest, lock })
+    }
+
+    /// Get the locked version of a dependency of the given package.
+    ///
+    /// The lock entry of the package lists its dependencies, either as `name` or, if there are
+    /// several versions of that dependency in the lock file, as `name version`.
+    fn locked_version(&self, package: &Manifest, dependency: &str) -> Option<&str> {
+        let lock_packages: Vec<&Table> = self
+            .lock
+            .as_ref()?
+            .get("package")
+            .and_then(Value::as_array)?
+            .iter()
+            .filter_map(Value::as_table)
+            .collect();
+
+        let entry = lock_packages.iter().find(|entry| {
+            field(entry, "name") == package.package_name()
+                && field(entry, "version") == package.package_version()
+        });
+        let listed = entry
+            .and_then(|entry| entry.get("dependencies"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|listed| listed.split(' '))
+            .find_map(|mut listed| match listed.next() {
+                Some(name) if name == dependency => Some(listed.next()),
+                _ => None,
+            });
+        if let Some(Some(version)) = listed {
+            return Some(version);
+        }
+
+        // The dependency is only locked in a single version.
+        lock_packages
+            .iter()
+            .find(|entry| field(entry, "name") == Some(dependency))
+            .and_then(|entry| field(entry, "version"))
+    }
+
+    /// Get all directories that may contain vendored sources.
+    ///
+    /// These are the `directory` sources of the cargo configurations of the workspace, as well as
+    /// the `vendor` directory that's used by `cargo vendor` by default.
+    fn vendor_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![self.manifest.dir.join("vendor")];
+
+        // Relative paths in the configuration are relative to the parent of the `.cargo` directory.
+        let configs = self
+            .manifest
+            .dir
+            .ancestors()
+            .map(|dir| (dir.to_path_buf(), dir.join(".cargo")))
+            .chain(cargo_home().map(|home| (home.clone(), home)));
+        for (base, config_dir) in configs {
+            for name in ["config.toml", "config"] {
+                // Configurations that are created or changed later on invalidate the resolution.
+                let path = config_dir.join(name);
+                cache::record(&path);
+                let content = match std::fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    Err(_) => continue,
+                };
+                let config = match content.parse::<Table>() {
+                    Ok(config) => config,
+                    Err(_) => continue,
+                };
+
+                let sources = config
+                    .get("source")
+                    .and_then(Value::as_table)
+                    .into_iter()
+                    .flat_map(|sources| sources.values())
+                    .filter_map(|source| source.get("directory"))
+                    .filter_map(Value::as_str);
+                dirs.extend(sources.map(|dir| base.join(dir)));
+            }
+        }
+
+        dirs
+    }
+}
+
+/// A crate whose source files can be searched for structs.
+pub struct Crate {
+    /// The root file of the crate, i.e. `src/lib.rs`.
+    pub root: PathBuf,
+    /// The manifest of the package the crate belongs to.
+    pub manifest: Manifest,
+    /// The workspace that's currently being compiled.
+    /// Dependencies share the workspace of the crate that depends on them.
+    pub workspace: Rc<Workspace>,
+    /// Whether this crate is a dependency of the crate that's being compiled.
+    /// The enabled features of dependencies are unknown.
+    pub is_dependency: bool,
+    /// The `inter-struct.toml` of the package.
+    pub config: Config,
+}
+
+impl Crate {
+    /// Whether a crate is available under the given crate name, either as a dependency or via
+    /// the `[crates]` of the configuration.
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.configured_crate(name).is_some() || self.manifest.has_dependency(name)
+    }
+
+    /// Get the library crate of the dependency that's available under the given crate name.
+    ///
+    /// Returns `Ok(None)` if the package doesn't have such a dependency.
+    ///
+    /// Crates that are located via the `[crates]` of the configuration take precedence.
+    /// Path dependencies are read directly from their directory.
+    /// The sources of registry dependencies are located via the `Cargo.lock` of the workspace,
+    /// either in a vendor directory or in the local registry cache of cargo.
+    /// Nothing is ever downloaded.
+    pub fn dependency(&self, name: &str, span: Span) -> Result<Option<Crate>, TokenStream> {
+        if let Some(dir) = self.configured_crate(name) {
+            return self.library(name, dir, span).map(Some);
+        }
+
+        let (key, dependency) = match self.manifest.dependency_table(name) {
+            Some(dependency) => dependency,
+            None => return Ok(None),
+        };
+
+        // Dependencies may inherit their declaration from the workspace.
+        let inherited = dependency
+            .and_then(|dependency| dependency.get("workspace"))
+            .and_then(Value::as_bool)
+            == Some(true);
+        let (declared_in, declaration) = if inherited {
+            let workspace = &self.workspace.manifest;
+            match workspace.workspace_dependency(key) {
+                Some(declaration) => (workspace, declaration),
+                None => {
+                    return Err(err!(
+                        span,
+                        "Dependency '{}' in {:?} is inherited from the workspace, but isn't declared in {:?}",
+                        key,
+                        self.manifest.dir.join("Cargo.toml"),
+                        workspace.dir.join("Cargo.toml")
+                    ))
+                }
+            }
+        } else {
+            (&self.manifest, dependency)
+        };
+        let option = |name: &str| {
+            declaration
+                .and_then(|declaration| declaration.get(name))
+                .and_then(Value::as_str)
+        };
+
+        let dir = if let Some(path) = option("path") {
+            declared_in.dir.join(path)
+        } else if option("git").is_some() {
+            return Err(err!(
+                span,
+                "Dependency '{}' in {:?} is a git dependency, which isn't supported.",
+                key,
+                declared_in.dir.join("Cargo.toml")
+            ));
+        } else {
+            // The dependency might have been renamed via `package = "..."`.
+            let package = option("package").unwrap_or(key);
+            self.registry_dependency_dir(package, span)?
+        };
+
+        self.library(key, dir, span).map(Some)
+    }
+
+    /// Get the package directory of a crate from the `[crates]` of the configuration.
+    fn configured_crate(&self, name: &str) -> Option<PathBuf> {
+        self.config
+            .crates
+            .iter()
+            .find(|(key, _)| is_same_name(key, name))
+            .map(|(_, dir)| dir.clone())
+    }
+
+    /// Load the library crate of the dependency `key` in the given package directory.
+    fn library(&self, key: &str, dir: PathBuf, span: Span) -> Result<Crate, TokenStream> {
+        let manifest = Manifest::read(&dir, span)?;
+        let config = Config::read(&dir, span)?;
+        let root = match config.root.clone().or_else(|| manifest.lib_root()) {
+            Some(root) => root,
+            None => {
+                return Err(err!(
+                    span,
+                    "Dependency '{}' in {:?} doesn't have a library target",
+                    key,
+                    dir
+                ))
+            }
+        };
+
+        Ok(Crate {
+            root,
+            manifest,
+            workspace: self.workspace.clone(),
+            is_dependency: true,
+            config,
+        })
+    }
+
+    /// Find the unpacked sources of a registry dependency of this crate.
+    fn registry_dependency_dir(&self, package: &str, span: Span) -> Result<PathBuf, TokenStream> {
+        let version = match self.workspace.locked_version(&self.manifest, package) {
+            Some(version) => version,
+            None => {
+                return Err(err!(
+                    span,
+                    "Couldn't find dependency '{}' in {:?}. Please run 'cargo generate-lockfile' first.",
+                    package,
+                    self.workspace.manifest.dir.join("Cargo.lock")
+                ))
+            }
+        };
+        let versioned_name = format!("{package}-{version}");
+
+        // `cargo vendor` only adds the version to the directory name if there are multiple
+        // versions of a crate.
+        for vendor_dir in self.workspace.vendor_dirs() {
+            if let Some(dir) = existing(vendor_dir.join(&versioned_name)) {
+                return Ok(dir);
+            }
+
+            // Directories without a readable manifest aren't vendored crates.
+            let dir = vendor_dir.join(package);
+            let manifest = Manifest::read(&dir, span).ok();
+            if manifest.is_some_and(|manifest| manifest.package_version() == Some(version)) {
+                return Ok(dir);
+            }
+        }
+
+        // Cargo unpacks all downloaded crates into `$CARGO_HOME/registry/src/<registry>/`.
+        // The directory changes, if a registry is added.
+        let registries = cargo_home()
+            .map(|home| home.join("registry").join("src"))
+            .and_then(|dir| {
+                cache::record(&dir);
+                std::fs::read_dir(dir).ok()
+            })
+            .into_iter()
+            .flatten()
+            .flatten();
+        for registry in registries {
+            if let Some(dir) = existing(registry.path().join(&versioned_name)) {
+                return Ok(dir);
+            }
+        }
+
+        Err(err!(
+            span,
+            "Couldn't find the sources of dependency '{}' in version {}. Please run 'cargo fetch' or 'cargo vendor' first.",
+            package,
+            version
+        ))
+    }
+}
+
+/// Get a string field of a `[[package]]` entry in the lock file.
+fn field<'a>(entry: &'a Table, name: &str) -> Option<&'a str> {
+    entry.get(name).and_then(Value::as_str)
+}
+
+/// The home directory of cargo.
+///
+/// That's either `$CARGO_HOME` or `.cargo` in the home directory of the user.
+fn cargo_home() -> Option<PathBuf> {
+    if let Some(home) = std::env::var_os("CARGO_HOME") {
+        return Some(PathBuf::from(home));
+    }
+
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| Path::new(&home).join(".cargo"))
+}
@@ -0,0 +1,158 @@
+//! The callback resolution mode, which doesn't read any files.
+//!
+//! Target structs derive `InterStructTarget`, which emits a hidden `macro_rules!` macro next to
+//! the struct. The source derive then expands into a call to that macro, which hands the source
+//! struct over to the target. The target macro adds its own struct definition and calls back into
+//! [expand], which generates the actual implementation.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, Ident, ItemStruct, Path, PathArguments};
+
+use super::{generate_impl, Mode, Parameters, Source};
+use crate::error::*;
+
+/// The actual logic for the InterStructTarget derive macro.
+pub fn target(struct_ast: TokenStream) -> TokenStream {
+    let target_struct: ItemStruct = match syn::parse2(struct_ast) {
+        Ok(target_struct) => target_struct,
+        Err(err) => return err.into_compile_error(),
+    };
+
+    let alias = macro_name(&target_struct.ident);
+    // Exported macros live in the root of their crate, which is why their name has to be unique.
+    let exported = format_ident!(
+        "__inter_struct_target_{}_{:016x}",
+        target_struct.ident,
+        unique_hash(&target_struct)
+    );
+
+    // Only the `#[cfg]` attributes of the fields are needed to generate the implementation.
+    let mut definition = target_struct;
+    definition.attrs.clear();
+    for field in definition.fields.iter_mut() {
+        field.attrs.retain(|attr| attr.path().is_ident("cfg"));
+    }
+
+    let tokens = quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #exported {
+            ($($request:tt)*) => {
+                inter_struct::__inter_struct_callback! { $($request)* { #definition } }
+            };
+        }
+
+        #[doc(hidden)]
+        pub use #exported as #alias;
+    };
+
+    #[cfg(feature = "debug")]
+    println!("InterStructTarget macro: {}", tokens);
+
+    tokens
+}
+
+/// Generate the call to the macro of a target struct, which derives `InterStructTarget`.
+///
+/// The macro is located next to the target struct, i.e. `crate::dto::User` becomes
+/// `crate::dto::__InterStructTarget_User!`.
+pub fn invoke(mode: Mode, source: &Source, target_path: Path) -> TokenStream {
+    let mut macro_path = target_path.clone();
+    if let Some(last) = macro_path.segments.last_mut() {
+        last.ident = macro_name(&last.ident);
+        last.arguments = PathArguments::None;
+    }
+    let mode = Ident::new(mode.name(), Span::call_site());
+    let src_path = &source.path;
+    let src_struct = &source.item;
+
+    quote! {
+        #macro_path! { #mode #target_path #src_path { #src_struct } }
+    }
+}
+
+/// The input of the callback macro.
+///
+/// `mode path::to::Target path::to::Source { source struct } { target struct }`
+struct Request {
+    mode: Ident,
+    target_path: Path,
+    src_path: Path,
+    src_struct: ItemStruct,
+    target_struct: ItemStruct,
+}
+
+impl Parse for Request {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mode = input.parse()?;
+        let target_path = input.parse()?;
+        let src_path = input.call(Path::parse_mod_style)?;
+        let src_content;
+        braced!(src_content in input);
+        let target_content;
+        braced!(target_content in input);
+
+        Ok(Request {
+            mode,
+            target_path,
+            src_path,
+            src_struct: src_content.parse()?,
+            target_struct: target_content.parse()?,
+        })
+    }
+}
+
+/// The actual logic of the macro, that's called by the macros of target structs.
+pub fn expand(input: TokenStream) -> TokenStream {
+    let request: Request = match syn::parse2(input) {
+        Ok(request) => request,
+        Err(err) => return err.into_compile_error(),
+    };
+
+    let mode = match Mode::from_name(&request.mode.to_string()) {
+        Some(mode) => mode,
+        None => {
+            return err!(
+                request.mode,
+                "Unknown inter_struct mode '{}'.",
+                request.mode
+            )
+        }
+    };
+
+    let params = Parameters {
+        src_struct: request.src_struct,
+        src_path: request.src_path,
+        target_path: request.target_path,
+        target_struct: request.target_struct,
+    };
+
+    // Type aliases of the configuration aren't available, as no files are read.
+    match generate_impl(&mode, params, &BTreeMap::new()) {
+        Ok(tokens) => tokens,
+        Err(error) => error,
+    }
+}
+
+/// The name of the macro that's emitted next to a target struct.
+fn macro_name(struct_name: &Ident) -> Ident {
+    format_ident!("__InterStructTarget_{}", struct_name)
+}
+
+/// Hash the location and the definition of a struct.
+fn unique_hash(item_struct: &ItemStruct) -> u64 {
+    let span = item_struct.ident.span();
+    let start = span.start();
+
+    let mut hasher = DefaultHasher::new();
+    span.local_file().hash(&mut hasher);
+    (start.line, start.column).hash(&mut hasher);
+    item_struct.to_token_stream().to_string().hash(&mut hasher);
+
+    hasher.finish()
+}
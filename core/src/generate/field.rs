@@ -1,31 +1,32 @@
 use proc_macro2::TokenStream;
-use syn::{GenericArgument, PathArguments, Type};
+use quote::ToTokens;
+use syn::{Attribute, Field, GenericArgument, PathArguments, Type};
 
 use crate::error::*;
+use crate::generate::types::{normalize_path, unwrap_type};
 
 /// Internal representation of parsed types
 ///
-/// We either expect fields to have a generic type `T` or `Option<T>`.
-/// Allow dead code, since this is what we're going to use as soon as proc macro hygiene has
-/// improved.
+/// Fields either have any type `T` (i.e. `String`, `&'a str` or `[u8; 32]`) or `Option<T>`.
 #[allow(clippy::large_enum_variant)]
-#[allow(dead_code)]
+#[derive(Clone)]
 pub enum FieldType {
     Normal(Type),
     Optional { inner: Type, outer: Type },
-    Invalid,
 }
 
 /// This function takes any [Type] and determines, whether it's an `Option<T>` or just a `T`.
 ///
 /// This detected variant is represented via the [FieldType] enum.
-/// Invalid or unsupported types return the `FieldType::Invalid` variant.
+/// Malformed options, i.e. `Option<'a>`, return an error.
 ///
 /// Known limitations:
 ///
-/// This doesn't work with type aliases. We literally check the tokens for `Option<...>`.
+/// This doesn't work with type aliases, which aren't listed in the configuration.
+/// We check the normalized path for `Option<...>`, which also detects `std::option::Option<...>`.
 /// If there's an optional type that doesn't look like this, we won't detect it.
 pub fn determine_field_type(ty: Type) -> Result<FieldType, TokenStream> {
+    let ty = unwrap_type(ty);
     match ty.clone() {
         Type::Path(type_path) => {
             // The path is relative to `Self` and thereby non-optional
@@ -33,7 +34,8 @@ pub fn determine_field_type(ty: Type) -> Result<FieldType, TokenStream> {
                 return Ok(FieldType::Normal(ty));
             }
 
-            let path = type_path.path;
+            let mut path = type_path.path;
+            normalize_path(&mut path);
 
             // `Option<T>` shouldn't have a leading colon or multiple segments.
             if path.leading_colon.is_some() || path.segments.len() > 1 {
@@ -78,9 +80,31 @@ pub fn determine_field_type(ty: Type) -> Result<FieldType, TokenStream> {
                 _ => Err(err!(ty, "Option path argument isn't a type.")),
             }
         }
-        _ => Err(err!(
-            ty,
-            "Found a non-path type. This isn't supported in inter-struct yet."
-        )),
+        // References, arrays, slices, tuples, function pointers and trait objects can't be
+        // optional. They're compared just like any other type.
+        _ => Ok(FieldType::Normal(ty)),
     }
 }
+
+/// Collect the `#[cfg(...)]` attributes of a source and a target field.
+///
+/// Generated code that uses both fields must only be compiled if both fields exist.
+/// Attributes that are present on both fields are only returned once.
+pub fn cfg_attributes(src_field: &Field, target_field: &Field) -> Vec<Attribute> {
+    let mut attributes: Vec<Attribute> = Vec::new();
+    for attr in src_field.attrs.iter().chain(target_field.attrs.iter()) {
+        if !attr.path().is_ident("cfg") {
+            continue;
+        }
+
+        let tokens = attr.to_token_stream().to_string();
+        if !attributes
+            .iter()
+            .any(|other| other.to_token_stream().to_string() == tokens)
+        {
+            attributes.push(attr.clone());
+        }
+    }
+
+    attributes
+}
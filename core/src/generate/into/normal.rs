@@ -1,30 +1,38 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::Field;
+use syn::parse_quote;
 
 use crate::error::*;
-use crate::generate::field::*;
+use crate::generate::field::FieldType;
+use crate::generate::mapping::*;
 use crate::generate::types::*;
-use crate::generate::*;
 
 /// Generate the [std::convert::From] for given structs.
-pub(crate) fn impl_into(
-    params: &Parameters,
-    fields: Vec<(Field, Field)>,
-    default_impl: bool,
-) -> TokenStream {
+pub(crate) fn impl_into(mapping: &StructMapping, default_impl: bool) -> TokenStream {
     let mut initializer_tokens = TokenStream::new();
 
     // Add `into` impl.
-    let stream = into(params, fields, default_impl);
+    let stream = into(mapping, default_impl);
     initializer_tokens.extend(vec![stream]);
 
+    // Missing fields are taken from the default of the target, which might depend on the
+    // generic parameters.
+    let src_type = &mapping.src_type;
+    let target_type = &mapping.target_type;
+    let mut predicates = Vec::new();
+    if default_impl && !mapping.generics.params.is_empty() {
+        predicates.push(parse_quote!(#target_type: Default));
+    }
+    let generics = mapping.generics_with(predicates);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
     // Surround the function with the correct Default  `impl` block.
-    let src_ident = &params.src_struct.ident;
-    let target_path = &params.target_path;
     quote! {
-        impl std::convert::From<#src_ident> for #target_path {
-            fn from(src: #src_ident) -> Self {
+        impl #impl_generics std::convert::From<#src_type> for #target_type #where_clause {
+            // The source isn't used, if there are no fields to take or if all of them are
+            // disabled by their `#[cfg]` attributes.
+            #[allow(unused_variables)]
+            fn from(src: #src_type) -> Self {
                 #initializer_tokens
             }
         }
@@ -32,31 +40,18 @@ pub(crate) fn impl_into(
 }
 
 /// Generate the [std::convert::From] function body for given structs.
-fn into(params: &Parameters, fields: Vec<(Field, Field)>, default_impl: bool) -> TokenStream {
+fn into(mapping: &StructMapping, default_impl: bool) -> TokenStream {
     let mut assignments = TokenStream::new();
     let mut errors = TokenStream::new();
+    errors.extend(mapping.errors.clone());
 
-    for (src_field, target_field) in fields {
-        let src_field_ident = src_field.ident;
-        let target_field_ident = target_field.ident;
-
-        // Find out, whether the fields are optional or not.
-        let src_field_type = match determine_field_type(src_field.ty) {
-            Ok(field) => field,
-            Err(err) => {
-                assignments.extend(vec![err]);
-                continue;
-            }
-        };
-        let target_field_type = match determine_field_type(target_field.ty) {
-            Ok(field) => field,
-            Err(err) => {
-                assignments.extend(vec![err]);
-                continue;
-            }
-        };
+    for field in mapping.fields.iter().cloned() {
+        // Initializers are only compiled, if both fields exist.
+        let cfg_attributes = field.cfg;
+        let src_member = field.src_member;
+        let target_member = field.target_member;
 
-        match (src_field_type, target_field_type) {
+        match (field.src_type, field.target_type) {
             // Both fields have the same type
             (FieldType::Normal(src_type), FieldType::Normal(target_type)) => {
                 if !is_equal_type(&src_type, &target_type) {
@@ -68,7 +63,8 @@ fn into(params: &Parameters, fields: Vec<(Field, Field)>, default_impl: bool) ->
                     )]);
                 } else {
                     let snippet = quote! {
-                        #target_field_ident: src.#src_field_ident,
+                        #(#cfg_attributes)*
+                        #target_member: src.#src_member,
                     };
                     assignments.extend(vec![snippet]);
                 }
@@ -101,7 +97,8 @@ fn into(params: &Parameters, fields: Vec<(Field, Field)>, default_impl: bool) ->
                     )]);
                 } else {
                     let snippet = quote! {
-                        #target_field_ident: Some(src.#src_field_ident),
+                        #(#cfg_attributes)*
+                        #target_member: Some(src.#src_member),
                     };
                     assignments.extend(vec![snippet]);
                 }
@@ -122,19 +119,11 @@ fn into(params: &Parameters, fields: Vec<(Field, Field)>, default_impl: bool) ->
             ) => {
                 // Handling the (Option<T>, Option<T>) case
                 if is_equal_type(&inner_src_type, &inner_target_type) {
-                    if !is_equal_type(&inner_src_type, &inner_target_type) {
-                        errors.extend(vec![err!(
-                            inner_src_type,
-                            "Type '{} cannot be merged into field of type '{}'.",
-                            inner_src_type.to_token_stream(),
-                            inner_target_type.to_token_stream()
-                        )]);
-                    } else {
-                        let snippet = quote! {
-                            #target_field_ident: src.#src_field_ident,
-                        };
-                        assignments.extend(vec![snippet]);
-                    }
+                    let snippet = quote! {
+                        #(#cfg_attributes)*
+                        #target_member: src.#src_member,
+                    };
+                    assignments.extend(vec![snippet]);
 
                     continue;
                 }
@@ -158,17 +147,16 @@ fn into(params: &Parameters, fields: Vec<(Field, Field)>, default_impl: bool) ->
                     )]);
                 } else {
                     let snippet = quote! {
-                        #target_field_ident: Some(src.#src_field_ident),
+                        #(#cfg_attributes)*
+                        #target_member: Some(src.#src_member),
                     };
                     assignments.extend(vec![snippet]);
                 }
             }
-            // Skip anything where either of the fields are invalid
-            (FieldType::Invalid, _) | (_, FieldType::Invalid) => continue,
         };
     }
 
-    let target_path = &params.target_path;
+    let target_path = &mapping.target_path;
     let assignment_code = assignments.to_token_stream();
     let error_code = errors.to_token_stream();
 
@@ -0,0 +1,496 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+use syn::{
+    parse_quote, Attribute, Expr, Field, Fields, GenericArgument, GenericParam, Generics, Index,
+    ItemStruct, Member, Path, PathArguments, Type, WherePredicate,
+};
+
+use crate::error::*;
+use crate::generate::field::{cfg_attributes, determine_field_type, FieldType};
+use crate::generate::types::{expand_type_aliases, Substitution};
+use crate::generate::Parameters;
+
+/// A source and a target struct, whose fields have been paired.
+///
+/// This is what all code is generated from.
+pub struct StructMapping {
+    pub src_struct: ItemStruct,
+    /// The path to the source struct, as it's used in the generated code.
+    pub src_path: Path,
+    pub target_struct: ItemStruct,
+    /// The path to the target struct, as it's used in the generated code.
+    pub target_path: Path,
+    /// The generic parameters and where-clauses of both structs.
+    /// Parameters with the same name are the same parameter.
+    pub generics: Generics,
+    /// The type of the source struct, i.e. `crate::Page<T>`.
+    pub src_type: Type,
+    /// The type of the target struct, i.e. `crate::PageDto<T>`.
+    pub target_type: Type,
+    /// All fields that exist in both structs, in the order of the source struct.
+    pub fields: Vec<FieldMapping>,
+    /// Errors of fields whose types couldn't be classified.
+    /// These fields aren't part of [StructMapping::fields].
+    pub errors: Vec<TokenStream>,
+}
+
+/// A field of the source struct and the field of the target struct it's mapped to.
+///
+/// Fields are paired by their name, the fields of tuple structs by their index.
+/// Other pairs are specified via `#[inter_struct(field = ...)]` on the source field.
+#[derive(Clone)]
+pub struct FieldMapping {
+    pub src: Field,
+    pub target: Field,
+    /// How the source field is accessed, i.e. `name` or `0`.
+    pub src_member: Member,
+    /// How the target field is accessed, i.e. `name` or `0`.
+    pub target_member: Member,
+    /// The classified type of the source field.
+    pub src_type: FieldType,
+    /// The classified type of the target field.
+    pub target_type: FieldType,
+    /// The `#[cfg]` attributes of both fields.
+    /// Code that uses both fields must only be compiled, if both fields exist.
+    pub cfg: Vec<Attribute>,
+}
+
+impl StructMapping {
+    /// Pair the fields of both structs.
+    ///
+    /// The configured type aliases are replaced in the classified types of the fields.
+    pub(crate) fn new(
+        params: Parameters,
+        type_aliases: &BTreeMap<String, Type>,
+    ) -> Result<StructMapping, TokenStream> {
+        // Generic arguments of the target path are substituted into the target struct.
+        let (target_path, target_generics, mut substitution) =
+            instantiate(&params.target_path, &params.target_struct.generics)?;
+
+        let target_fields = members(&params.target_struct.fields);
+        let target_is_named = matches!(params.target_struct.fields, Fields::Named(_));
+        // Unit targets don't have any fields, which is why nothing is mapped.
+        let src_fields = match params.target_struct.fields {
+            Fields::Unit => Vec::new(),
+            _ => members(&params.src_struct.fields),
+        };
+
+        let mut fields: Vec<FieldMapping> = Vec::new();
+        let mut errors = Vec::new();
+        for (src_member, src_field) in src_fields {
+            // Explicit target fields of the wrong kind are meant for other targets.
+            let mut explicit = crate::parse::field_targets(&src_field)?
+                .into_iter()
+                .filter(|member| matches!(member, Member::Named(_)) == target_is_named);
+            let (target_member, target_field) = match (explicit.next(), explicit.next()) {
+                (Some(_), Some(second)) => {
+                    return Err(err!(
+                        second,
+                        "Only a single target field of the same kind is allowed per field."
+                    ));
+                }
+                (Some(member), None) => {
+                    match target_fields.iter().find(|(other, _)| *other == member) {
+                        Some(target) => target.clone(),
+                        None => {
+                            return Err(err!(
+                                member,
+                                "The target struct doesn't have a field '{}'.",
+                                member.to_token_stream()
+                            ));
+                        }
+                    }
+                }
+                // Without an attribute, fields are paired by their name or by their index.
+                (None, _) => match target_fields.iter().find(|(other, _)| *other == src_member) {
+                    Some(target) => target.clone(),
+                    None => continue,
+                },
+            };
+
+            if fields
+                .iter()
+                .any(|field| field.target_member == target_member)
+            {
+                return Err(err!(
+                    src_member,
+                    "Multiple fields are mapped to the field '{}' of the target struct.",
+                    target_member.to_token_stream()
+                ));
+            }
+
+            let mut src_type = src_field.ty.clone();
+            let mut target_type = target_field.ty.clone();
+            substitution.visit_type_mut(&mut target_type);
+            expand_type_aliases(&mut src_type, type_aliases);
+            expand_type_aliases(&mut target_type, type_aliases);
+
+            // Find out, whether the fields are optional or not.
+            let (src_type, target_type) = match (
+                determine_field_type(src_type),
+                determine_field_type(target_type),
+            ) {
+                (Ok(src_type), Ok(target_type)) => (src_type, target_type),
+                (Err(err), _) | (_, Err(err)) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+
+            fields.push(FieldMapping {
+                cfg: cfg_attributes(&src_field, &target_field),
+                src: src_field,
+                target: target_field,
+                src_member,
+                target_member,
+                src_type,
+                target_type,
+            });
+        }
+
+        Ok(StructMapping {
+            generics: merge_generics(&params.src_struct.generics, &target_generics),
+            src_type: struct_type(&params.src_path, &params.src_struct.generics),
+            target_type: instantiated_type(
+                &target_path,
+                &params.target_struct.generics,
+                &substitution,
+            ),
+            src_struct: params.src_struct,
+            src_path: params.src_path,
+            target_struct: params.target_struct,
+            target_path,
+            fields,
+            errors,
+        })
+    }
+
+    /// Whether the given type uses one of the generic type parameters.
+    pub fn uses_type_params(&self, ty: &Type) -> bool {
+        let names: Vec<String> = self
+            .generics
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .collect();
+
+        !names.is_empty() && contains_ident(ty.to_token_stream(), &names)
+    }
+
+    /// The generics with additional where-clause predicates.
+    pub fn generics_with(&self, predicates: Vec<WherePredicate>) -> Generics {
+        let mut generics = self.generics.clone();
+        if !predicates.is_empty() {
+            generics.make_where_clause().predicates.extend(predicates);
+        }
+
+        generics
+    }
+}
+
+/// Combine the generic parameters and where-clauses of both structs.
+///
+/// Parameters with the same name are merged, including their bounds.
+/// Lifetimes have to be declared before all other parameters.
+fn merge_generics(src: &Generics, target: &Generics) -> Generics {
+    let mut lifetimes: Vec<GenericParam> = Vec::new();
+    let mut params: Vec<GenericParam> = Vec::new();
+    for param in src.params.iter().chain(target.params.iter()) {
+        let list = match param {
+            GenericParam::Lifetime(_) => &mut lifetimes,
+            _ => &mut params,
+        };
+
+        let existing = list
+            .iter_mut()
+            .find(|existing| param_name(existing) == param_name(param));
+        match (existing, param) {
+            (None, _) => list.push(param.clone()),
+            (Some(GenericParam::Type(existing)), GenericParam::Type(param)) => {
+                for bound in &param.bounds {
+                    let bound_string = bound.to_token_stream().to_string();
+                    if !existing
+                        .bounds
+                        .iter()
+                        .any(|other| other.to_token_stream().to_string() == bound_string)
+                    {
+                        existing.bounds.push(bound.clone());
+                    }
+                }
+            }
+            (Some(GenericParam::Lifetime(existing)), GenericParam::Lifetime(param)) => {
+                for bound in &param.bounds {
+                    if !existing.bounds.iter().any(|other| other == bound) {
+                        existing.bounds.push(bound.clone());
+                    }
+                }
+            }
+            // Const parameters can't have bounds.
+            (Some(_), _) => (),
+        }
+    }
+
+    let mut generics = Generics::default();
+    if !lifetimes.is_empty() || !params.is_empty() {
+        generics.lt_token = Some(Default::default());
+        generics.gt_token = Some(Default::default());
+    }
+    generics.params.extend(lifetimes);
+    generics.params.extend(params);
+
+    let predicates: Vec<WherePredicate> = src
+        .where_clause
+        .iter()
+        .chain(target.where_clause.iter())
+        .flat_map(|where_clause| where_clause.predicates.iter().cloned())
+        .collect();
+    if !predicates.is_empty() {
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+
+    generics
+}
+
+/// Split the generic arguments off the target path, i.e. `crate::Page<crate::User>`.
+///
+/// Returns the plain path, the generics of the target that remain and the arguments by the names
+/// of the parameters they replace. Lifetimes may be omitted, they then remain generic.
+fn instantiate(
+    path: &Path,
+    generics: &Generics,
+) -> Result<(Path, Generics, Substitution), TokenStream> {
+    let mut plain_path = path.clone();
+    let arguments = match plain_path.segments.last_mut() {
+        Some(last) => std::mem::replace(&mut last.arguments, PathArguments::None),
+        None => PathArguments::None,
+    };
+    let arguments = match arguments {
+        PathArguments::None => return Ok((plain_path, generics.clone(), Substitution::default())),
+        PathArguments::AngleBracketed(arguments) => arguments,
+        PathArguments::Parenthesized(arguments) => {
+            return Err(err!(
+                arguments,
+                "Only generic arguments are allowed in target paths."
+            ))
+        }
+    };
+
+    let mut lifetimes = Vec::new();
+    let mut others = Vec::new();
+    for argument in arguments.args.iter() {
+        match argument {
+            GenericArgument::Lifetime(lifetime) => lifetimes.push(lifetime),
+            GenericArgument::Type(_) | GenericArgument::Const(_) => others.push(argument),
+            _ => {
+                return Err(err!(
+                    argument,
+                    "Only lifetimes, types and constants are allowed as generic arguments."
+                ))
+            }
+        }
+    }
+
+    let lifetime_params: Vec<_> = generics.lifetimes().collect();
+    let other_params: Vec<_> = generics
+        .params
+        .iter()
+        .filter(|param| !matches!(param, GenericParam::Lifetime(_)))
+        .collect();
+    let lifetimes_match = lifetimes.is_empty() || lifetimes.len() == lifetime_params.len();
+    if !lifetimes_match || others.len() != other_params.len() {
+        return Err(err!(
+            arguments,
+            "The target struct has {} generic parameters and {} lifetimes, but {} generic arguments and {} lifetimes were given.",
+            other_params.len(),
+            lifetime_params.len(),
+            others.len(),
+            lifetimes.len()
+        ));
+    }
+
+    let mut substitution = Substitution::default();
+    for (param, lifetime) in lifetime_params.iter().zip(lifetimes) {
+        substitution
+            .lifetimes
+            .insert(param.lifetime.to_string(), lifetime.clone());
+    }
+    for (param, argument) in other_params.into_iter().zip(others) {
+        match (param, argument) {
+            (GenericParam::Type(param), GenericArgument::Type(ty)) => {
+                substitution
+                    .types
+                    .insert(param.ident.to_string(), ty.clone());
+            }
+            (GenericParam::Const(param), GenericArgument::Const(expr)) => {
+                substitution
+                    .consts
+                    .insert(param.ident.to_string(), expr.clone());
+            }
+            // Constants that are a plain path are parsed as types, i.e. `Page<LIMIT>`.
+            (GenericParam::Const(param), GenericArgument::Type(ty)) => {
+                let expr: Expr = parse_quote!(#ty);
+                substitution.consts.insert(param.ident.to_string(), expr);
+            }
+            (param, argument) => {
+                return Err(err!(
+                    argument,
+                    "'{}' isn't a valid argument for the generic parameter '{}'.",
+                    argument.to_token_stream(),
+                    param_name(param)
+                ))
+            }
+        }
+    }
+
+    // The replaced parameters are gone, their where-clauses now apply to the arguments.
+    let mut remaining = generics.clone();
+    remaining.params = generics
+        .params
+        .iter()
+        .filter(|param| !substitution.replaces(param))
+        .cloned()
+        .collect();
+    if let Some(where_clause) = &mut remaining.where_clause {
+        substitution.visit_where_clause_mut(where_clause);
+    }
+
+    Ok((plain_path, remaining, substitution))
+}
+
+/// Substitute the generic arguments of a type alias into the struct it points to.
+/// I.e. `type UserPage = Page<User>;` turns `struct Page<T>` into a struct without parameters.
+///
+/// The struct then has the generic parameters of the alias, so it can be used via the alias path.
+pub(crate) fn instantiate_alias(
+    mut item_struct: ItemStruct,
+    path: &Path,
+    alias_generics: &Generics,
+) -> Result<ItemStruct, TokenStream> {
+    let (_, remaining, mut substitution) = instantiate(path, &item_struct.generics)?;
+    if substitution.is_empty() {
+        return Ok(item_struct);
+    }
+    substitution.visit_fields_mut(&mut item_struct.fields);
+
+    // Only omitted lifetimes remain, which have to be declared before the parameters of the alias.
+    let mut generics = alias_generics.clone();
+    generics.params = remaining
+        .params
+        .into_iter()
+        .chain(alias_generics.params.iter().cloned())
+        .collect();
+    if generics.params.is_empty() {
+        generics.lt_token = None;
+        generics.gt_token = None;
+    } else {
+        generics.lt_token = Some(Default::default());
+        generics.gt_token = Some(Default::default());
+    }
+    let predicates: Vec<WherePredicate> = remaining
+        .where_clause
+        .into_iter()
+        .flat_map(|where_clause| where_clause.predicates)
+        .collect();
+    if !predicates.is_empty() {
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+    item_struct.generics = generics;
+
+    Ok(item_struct)
+}
+
+/// The type of the target struct with the given arguments, i.e. `path::Page<'a, crate::User>`.
+///
+/// Parameters that aren't replaced are passed on as they are.
+fn instantiated_type(path: &Path, generics: &Generics, substitution: &Substitution) -> Type {
+    if substitution.is_empty() {
+        return struct_type(path, generics);
+    }
+
+    let arguments = generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(param) => {
+            let lifetime = substitution
+                .lifetimes
+                .get(&param.lifetime.to_string())
+                .unwrap_or(&param.lifetime);
+            quote!(#lifetime)
+        }
+        GenericParam::Type(param) => match substitution.types.get(&param.ident.to_string()) {
+            Some(ty) => quote!(#ty),
+            None => param.ident.to_token_stream(),
+        },
+        GenericParam::Const(param) => match substitution.consts.get(&param.ident.to_string()) {
+            // Complex expressions have to be wrapped in braces.
+            Some(Expr::Lit(lit)) => quote!(#lit),
+            Some(expr) => quote!({ #expr }),
+            None => param.ident.to_token_stream(),
+        },
+    });
+
+    parse_quote!(#path<#(#arguments),*>)
+}
+
+/// The fields of a struct with the way they're accessed, i.e. `name` or `0`.
+fn members(fields: &Fields) -> Vec<(Member, Field)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), field.clone()),
+            None => {
+                let index = Index {
+                    index: index as u32,
+                    span: field.ty.span(),
+                };
+                (Member::Unnamed(index), field.clone())
+            }
+        })
+        .collect()
+}
+
+/// The name of a generic parameter.
+fn param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Lifetime(param) => param.lifetime.to_string(),
+        GenericParam::Type(param) => param.ident.to_string(),
+        GenericParam::Const(param) => param.ident.to_string(),
+    }
+}
+
+/// The type of a struct with its own generic parameters as arguments, i.e. `path::Page<'a, T>`.
+fn struct_type(path: &Path, generics: &Generics) -> Type {
+    let (_, type_generics, _) = generics.split_for_impl();
+    let tokens = quote!(#path #type_generics);
+
+    parse_quote!(#tokens)
+}
+
+/// Whether the tokens contain one of the given identifiers.
+fn contains_ident(tokens: TokenStream, names: &[String]) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(ident) => names.contains(&ident.to_string()),
+        TokenTree::Group(group) => contains_ident(group.stream(), names),
+        _ => false,
+    })
+}
+
+/// Generates code from a [StructMapping].
+///
+/// This is implemented by the built-in [Mode](crate::Mode)s.
+/// Other crates can implement it to write their own derives on top of the resolution of
+/// inter-struct, see [derive_with](crate::derive_with) and [expand_with](crate::expand_with).
+pub trait Generator {
+    /// The name of the derive macro, which is used in error messages.
+    fn derive_name(&self) -> &str;
+
+    /// The name of the attribute, which contains the targets of the derive macro.
+    fn attribute_name(&self) -> &str;
+
+    /// Generate the code for a pair of structs.
+    ///
+    /// The errors of the mapping should be part of the generated code.
+    fn generate(&self, mapping: &StructMapping) -> TokenStream;
+}
@@ -1,25 +1,44 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::Field;
+use syn::{parse_quote, WherePredicate};
 
 use crate::error::*;
-use crate::generate::field::*;
+use crate::generate::field::FieldType;
+use crate::generate::mapping::*;
 use crate::generate::types::*;
-use crate::generate::Parameters;
 
 /// Generate the implementation of [inter_struct::merge::StructMergeRef] for given structs.
-pub(crate) fn impl_borrowed(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
+pub(crate) fn impl_borrowed(mapping: &StructMapping) -> TokenStream {
     let mut functions_tokens = TokenStream::new();
 
     // Add `merge_ref` impl.
-    let stream = merge_ref(params, fields);
+    let stream = merge_ref(mapping);
     functions_tokens.extend(vec![stream]);
 
+    // Generic fields are cloned, which their types have to allow.
+    // Optional fields are unwrapped before they're cloned, and `T: Clone` also covers `Option<T>`.
+    let mut predicates: Vec<WherePredicate> = Vec::new();
+    for field in &mapping.fields {
+        let ty = match &field.src_type {
+            FieldType::Normal(ty) => ty,
+            FieldType::Optional { inner, .. } => inner,
+        };
+        let predicate: WherePredicate = parse_quote!(#ty: Clone);
+        let exists = predicates.iter().any(|other| {
+            other.to_token_stream().to_string() == predicate.to_token_stream().to_string()
+        });
+        if mapping.uses_type_params(ty) && !exists {
+            predicates.push(predicate);
+        }
+    }
+    let generics = mapping.generics_with(predicates);
+
     // Surround functions with `impl` block.
-    let src_ident = &params.src_struct.ident;
-    let target_path = &params.target_path;
+    let src_type = &mapping.src_type;
+    let target_type = &mapping.target_type;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
     quote! {
-        impl inter_struct::merge::StructMergeIntoRef<#target_path> for #src_ident {
+        impl #impl_generics inter_struct::merge::StructMergeIntoRef<#target_type> for #src_type #where_clause {
             #functions_tokens
         }
     }
@@ -28,36 +47,22 @@ pub(crate) fn impl_borrowed(params: &Parameters, fields: Vec<(Field, Field)>) ->
 /// Generate the [inter_struct::merge::StructMergeRef::merge_ref] function for given structs.
 ///
 /// All fields must implement `Clone`.
-fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
+fn merge_ref(mapping: &StructMapping) -> TokenStream {
     let mut merge_code = TokenStream::new();
-    for (src_field, target_field) in fields {
-        let src_field_ident = src_field.ident;
-        let target_field_ident = target_field.ident;
+    merge_code.extend(mapping.errors.clone());
+    for field in mapping.fields.iter().cloned() {
+        let cfg_attributes = field.cfg;
+        let src_member = field.src_member;
+        let target_member = field.target_member;
 
-        // Find out, whether the fields are optional or not.
-        let src_field_type = match determine_field_type(src_field.ty) {
-            Ok(field) => field,
-            Err(err) => {
-                merge_code.extend(vec![err]);
-                continue;
-            }
-        };
-        let target_field_type = match determine_field_type(target_field.ty) {
-            Ok(field) => field,
-            Err(err) => {
-                merge_code.extend(vec![err]);
-                continue;
-            }
-        };
-
-        let snippet = match (src_field_type, target_field_type) {
+        let snippet = match (field.src_type, field.target_type) {
             // Both fields have the same type
             (FieldType::Normal(src_type), FieldType::Normal(target_type)) => {
                 equal_type_or_err!(
                     src_type,
                     target_type,
                     quote! {
-                        target.#target_field_ident = self.#src_field_ident.clone();
+                        target.#target_member = self.#src_member.clone();
                     }
                 )
             }
@@ -72,8 +77,8 @@ fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                     src_type,
                     target_type,
                     quote! {
-                        if let Some(value) = self.#src_field_ident.as_ref() {
-                            target.#target_field_ident = value.clone();
+                        if let Some(value) = self.#src_member.as_ref() {
+                            target.#target_member = value.clone();
                         }
                     }
                 )
@@ -89,7 +94,7 @@ fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                     src_type,
                     target_type,
                     quote! {
-                        self.#target_field_ident = Some(src.#src_field_ident.clone());
+                        target.#target_member = Some(self.#src_member.clone());
                     }
                 )
             }
@@ -113,7 +118,7 @@ fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                         inner_src_type,
                         inner_target_type,
                         quote! {
-                            target.#target_field_ident = self.#src_field_ident.clone();
+                            target.#target_member = self.#src_member.clone();
                         }
                     )
                 // Handling the (Option<Option<<T>>, Option<T>) case
@@ -122,8 +127,8 @@ fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                         inner_src_type,
                         outer_target_type,
                         quote! {
-                            if let Some(value) = self.#src_field_ident.as_ref() {
-                                target.#target_field_ident = value.clone();
+                            if let Some(value) = self.#src_member.as_ref() {
+                                target.#target_member = value.clone();
                             }
                         }
                     )
@@ -133,13 +138,23 @@ fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                         outer_src_type,
                         inner_target_type,
                         quote! {
-                            target.#target_field_ident = Some(self.#src_field_ident.clone());
+                            target.#target_member = Some(self.#src_member.clone());
                         }
                     )
                 }
             }
-            // Skip anything where either of the fields are invalid
-            (FieldType::Invalid, _) | (_, FieldType::Invalid) => continue,
+        };
+
+        // The code is only compiled, if both fields exist.
+        let snippet = if cfg_attributes.is_empty() {
+            snippet
+        } else {
+            quote! {
+                #(#cfg_attributes)*
+                {
+                    #snippet
+                }
+            }
         };
 
         merge_code.extend(vec![snippet]);
@@ -147,9 +162,12 @@ fn merge_ref(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
 
     let merge_code = merge_code.to_token_stream();
 
-    let target_path = &params.target_path;
+    let target_type = &mapping.target_type;
     quote! {
-        fn merge_into_ref(&self, target: &mut #target_path) {
+        // The target isn't used, if there are no fields to merge or if all of them are disabled
+        // by their `#[cfg]` attributes.
+        #[allow(unused_variables)]
+        fn merge_into_ref(&self, target: &mut #target_type) {
             #merge_code
         }
     }
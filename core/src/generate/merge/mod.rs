@@ -0,0 +1,2 @@
+pub mod borrowed;
+pub mod owned;
@@ -1,61 +1,48 @@
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::Field;
 
 use crate::error::*;
-use crate::generate::field::*;
+use crate::generate::field::FieldType;
+use crate::generate::mapping::*;
 use crate::generate::types::*;
-use crate::generate::Parameters;
 
 /// Generate the implementation of [inter_struct::merge::StructMerge] for given structs.
-pub(crate) fn impl_owned(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
+pub(crate) fn impl_owned(mapping: &StructMapping) -> TokenStream {
     let mut functions_tokens = TokenStream::new();
 
     // Add `merge` impl.
-    let stream = merge(params, fields);
+    let stream = merge(mapping);
     functions_tokens.extend(vec![stream]);
+    let generics = &mapping.generics;
 
     // Surround functions with `impl` block.
-    let src_ident = &params.src_struct.ident;
-    let target_path = &params.target_path;
+    let src_type = &mapping.src_type;
+    let target_type = &mapping.target_type;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
     quote! {
-        impl inter_struct::merge::StructMergeInto<#target_path> for #src_ident {
+        impl #impl_generics inter_struct::merge::StructMergeInto<#target_type> for #src_type #where_clause {
             #functions_tokens
         }
     }
 }
 
 /// Generate the [inter_struct::merge::StructMerge::merge] function for the given structs.
-fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
+fn merge(mapping: &StructMapping) -> TokenStream {
     let mut merge_code = TokenStream::new();
-    for (src_field, target_field) in fields {
-        let src_field_ident = src_field.ident;
-        let target_field_ident = target_field.ident;
+    merge_code.extend(mapping.errors.clone());
+    for field in mapping.fields.iter().cloned() {
+        let cfg_attributes = field.cfg;
+        let src_member = field.src_member;
+        let target_member = field.target_member;
 
-        // Find out, whether the fields are optional or not.
-        let src_field_type = match determine_field_type(src_field.ty) {
-            Ok(field) => field,
-            Err(err) => {
-                merge_code.extend(vec![err]);
-                continue;
-            }
-        };
-        let target_field_type = match determine_field_type(target_field.ty) {
-            Ok(field) => field,
-            Err(err) => {
-                merge_code.extend(vec![err]);
-                continue;
-            }
-        };
-
-        let snippet = match (src_field_type, target_field_type) {
+        let snippet = match (field.src_type, field.target_type) {
             // Both fields have the same type
             (FieldType::Normal(src_type), FieldType::Normal(target_type)) => {
                 equal_type_or_err!(
                     src_type,
                     target_type,
                     quote! {
-                        target.#target_field_ident = self.#src_field_ident;
+                        target.#target_member = self.#src_member;
                     }
                 )
             }
@@ -70,8 +57,8 @@ fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                     src_type,
                     target_type,
                     quote! {
-                        if let Some(value) = self.#src_field_ident {
-                            target.#target_field_ident = value;
+                        if let Some(value) = self.#src_member {
+                            target.#target_member = value;
                         }
                     }
                 )
@@ -87,7 +74,7 @@ fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                     src_type,
                     target_type,
                     quote! {
-                        target.#target_field_ident = Some(self.#src_field_ident);
+                        target.#target_member = Some(self.#src_member);
                     }
                 )
             }
@@ -111,7 +98,7 @@ fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                         inner_src_type,
                         inner_target_type,
                         quote! {
-                            target.#target_field_ident = self.#src_field_ident;
+                            target.#target_member = self.#src_member;
                         }
                     )
                 // Handling the (Option<Option<<T>>, Option<T>) case
@@ -120,8 +107,8 @@ fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                         inner_src_type,
                         outer_target_type,
                         quote! {
-                            if let Some(value) = self.#src_field_ident {
-                                target.#target_field_ident = value;
+                            if let Some(value) = self.#src_member {
+                                target.#target_member = value;
                             }
                         }
                     )
@@ -131,13 +118,23 @@ fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
                         outer_src_type,
                         inner_target_type,
                         quote! {
-                            target.#target_field_ident = Some(self.#src_field_ident);
+                            target.#target_member = Some(self.#src_member);
                         }
                     )
                 }
             }
-            // Skip anything where either of the fields are invalid
-            (FieldType::Invalid, _) | (_, FieldType::Invalid) => continue,
+        };
+
+        // The code is only compiled, if both fields exist.
+        let snippet = if cfg_attributes.is_empty() {
+            snippet
+        } else {
+            quote! {
+                #(#cfg_attributes)*
+                {
+                    #snippet
+                }
+            }
         };
 
         merge_code.extend(vec![snippet]);
@@ -145,9 +142,12 @@ fn merge(params: &Parameters, fields: Vec<(Field, Field)>) -> TokenStream {
 
     let merge_code = merge_code.to_token_stream();
 
-    let target_path = &params.target_path;
+    let target_type = &mapping.target_type;
     quote! {
-        fn merge_into(self, target: &mut #target_path) {
+        // The target isn't used, if there are no fields to merge or if all of them are disabled
+        // by their `#[cfg]` attributes.
+        #[allow(unused_variables)]
+        fn merge_into(self, target: &mut #target_type) {
             #merge_code
         }
     }
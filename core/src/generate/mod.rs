@@ -0,0 +1,481 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Attribute, Expr, Fields, ItemStruct, Member, Path, Type};
+
+use crate::dependency::Crate;
+use crate::error::err;
+use crate::helper::get_crate;
+use crate::module::{get_struct_by_name, get_struct_from_path};
+use crate::parse::{self, Target};
+use crate::scan::is_generated_by_build_script;
+
+/// Some helper functions and macros, that need to be declared before the actual generaction code.
+mod field;
+mod types;
+
+pub mod callback;
+pub mod into;
+mod mapping;
+pub mod merge;
+
+pub use field::FieldType;
+pub(crate) use mapping::instantiate_alias;
+pub use mapping::{FieldMapping, Generator, StructMapping};
+
+pub(crate) struct Parameters {
+    pub src_struct: ItemStruct,
+    /// The path to the source struct, as it's used in the generated code.
+    pub src_path: Path,
+    pub target_path: Path,
+    pub target_struct: ItemStruct,
+}
+
+/// This enum is used to differentiate between the different implementations of the InterStruct
+/// derive macro.
+#[derive(Clone, Copy)]
+pub enum Mode {
+    Merge,
+    MergeRef,
+    Into,
+    IntoDefault,
+}
+
+impl Mode {
+    /// All modes, which is used to find the derives of structs.
+    pub const ALL: [Mode; 4] = [Mode::Merge, Mode::MergeRef, Mode::Into, Mode::IntoDefault];
+
+    /// The name of the mode, as it's passed to target structs in callback mode.
+    fn name(&self) -> &'static str {
+        match self {
+            Mode::Merge => "merge",
+            Mode::MergeRef => "merge_ref",
+            Mode::Into => "into",
+            Mode::IntoDefault => "into_default",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Mode> {
+        Mode::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    /// The name of the derive macro.
+    pub fn derive_name(&self) -> &'static str {
+        match self {
+            Mode::Merge => "StructMerge",
+            Mode::MergeRef => "StructMergeRef",
+            Mode::Into => "StructInto",
+            Mode::IntoDefault => "StructIntoDefault",
+        }
+    }
+
+    /// The name of the attribute, which contains the targets of the derive macro.
+    pub fn attribute_name(&self) -> &'static str {
+        match self {
+            Mode::Merge => "struct_merge",
+            Mode::MergeRef => "struct_merge_ref",
+            Mode::Into => "struct_into",
+            Mode::IntoDefault => "struct_into_default",
+        }
+    }
+}
+
+impl Generator for Mode {
+    fn derive_name(&self) -> &str {
+        Mode::derive_name(self)
+    }
+
+    fn attribute_name(&self) -> &str {
+        Mode::attribute_name(self)
+    }
+
+    fn generate(&self, mapping: &StructMapping) -> TokenStream {
+        match self {
+            Mode::Merge => merge::owned::impl_owned(mapping),
+            Mode::MergeRef => merge::borrowed::impl_borrowed(mapping),
+            Mode::Into => into::normal::impl_into(mapping, false),
+            Mode::IntoDefault => into::normal::impl_into(mapping, true),
+        }
+    }
+}
+
+/// A struct that derives one of the macros.
+pub struct Source {
+    /// The definition of the struct.
+    pub item: ItemStruct,
+    /// The path to the struct, as it's used in the generated code.
+    pub path: Path,
+    /// The file that contains the struct.
+    pub file: Option<PathBuf>,
+    /// Whether the generated code is placed next to the struct.
+    /// Only then target paths can be relative to the module of the struct.
+    pub in_place: bool,
+}
+
+impl Source {
+    /// The input of a derive macro. The compiler provides the file that contains the struct.
+    pub fn from_derive_input(item: ItemStruct) -> Source {
+        Source {
+            path: Path::from(item.ident.clone()),
+            file: item.ident.span().local_file(),
+            item,
+            in_place: true,
+        }
+    }
+}
+
+/// The actual logic of the derive macros.
+pub fn derive(mode: Mode, struct_ast: TokenStream) -> TokenStream {
+    // Parse the main macro input as a struct.
+    let src_struct: ItemStruct = match syn::parse2(struct_ast) {
+        Ok(src_struct) => src_struct,
+        Err(err) => return err.into_compile_error(),
+    };
+
+    // The implementations are already generated by the build script of the crate.
+    if is_generated_by_build_script() {
+        return TokenStream::new();
+    }
+
+    let impls = expand(mode, &Source::from_derive_input(src_struct));
+
+    // Merge all generated pieces of the code.
+    let mut tokens = TokenStream::new();
+    tokens.extend(impls);
+
+    #[cfg(feature = "debug")]
+    println!("{} impl: {}", mode.derive_name(), tokens);
+
+    tokens
+}
+
+/// The logic of a derive macro with a custom [Generator].
+///
+/// The targets in the attribute of the struct are resolved just like the ones of the built-in
+/// derive macros. Callback targets aren't supported, as the macros of target structs only know
+/// the built-in modes.
+/// The derive macro has to declare the `inter_struct` attribute, which specifies the target
+/// fields of source fields.
+pub fn derive_with(generator: &dyn Generator, struct_ast: TokenStream) -> TokenStream {
+    let src_struct: ItemStruct = match syn::parse2(struct_ast) {
+        Ok(src_struct) => src_struct,
+        Err(err) => return err.into_compile_error(),
+    };
+
+    expand_source(generator, None, &Source::from_derive_input(src_struct))
+        .into_iter()
+        .collect()
+}
+
+/// Generate the implementations for all targets in the attribute of the source struct.
+pub fn expand(mode: Mode, source: &Source) -> Vec<TokenStream> {
+    expand_source(&mode, Some(mode), source)
+}
+
+/// Generate the code of the generator for all targets in the attribute of the source struct.
+///
+/// The mode is only known for the built-in generators, which can handle callback targets.
+fn expand_source(
+    generator: &dyn Generator,
+    mode: Option<Mode>,
+    source: &Source,
+) -> Vec<TokenStream> {
+    let attribute = match parse::attribute(
+        &source.item,
+        generator.derive_name(),
+        generator.attribute_name(),
+    ) {
+        Ok(attribute) => attribute,
+        Err(err) => return vec![err],
+    };
+
+    let parsed_args = match attribute.parse_args() {
+        Ok(parsed_args) => parsed_args,
+        Err(err) => return vec![err.into_compile_error()],
+    };
+
+    inter_struct_base(source, parsed_args, generator, mode)
+}
+
+fn inter_struct_base(
+    source: &Source,
+    parsed_args: Expr,
+    generator: &dyn Generator,
+    mode: Option<Mode>,
+) -> Vec<TokenStream> {
+    // Get the input targets from the given argument expressions.
+    let targets = crate::parse::input_targets(parsed_args);
+    let targets = match targets {
+        Ok(targets) => targets,
+        Err(err) => return vec![err],
+    };
+
+    // The crate is only loaded, if a target has to be searched in its files.
+    let mut krate: Option<Result<Rc<Crate>, TokenStream>> = None;
+    let mut load_crate = || match krate.get_or_insert_with(|| get_crate(source.item.ident.span())) {
+        Ok(krate) => Ok(krate.clone()),
+        // Only report the error for the first target.
+        Err(error) => Err(std::mem::take(error)),
+    };
+
+    // Go through all targets and process the respective struct.
+    let mut impls = Vec::new();
+    for target in targets {
+        // Generated code that's placed somewhere else can't use relative paths.
+        if let Target::Path(path) | Target::Callback(path) = &target {
+            if !source.in_place {
+                let is_relative = match load_crate() {
+                    Ok(krate) => is_relative(&krate, path),
+                    Err(error) => {
+                        impls.push(error);
+                        continue;
+                    }
+                };
+                if is_relative {
+                    impls.push(err!(
+                        path,
+                        "Relative paths aren't supported in build scripts, please use a 'crate::' path."
+                    ));
+                    continue;
+                }
+            }
+        }
+
+        // The target struct generates the implementation itself.
+        if let Target::Callback(target_path) = target {
+            match mode {
+                Some(mode) => impls.push(callback::invoke(mode, source, target_path)),
+                None => impls.push(err!(
+                    target_path,
+                    "{} doesn't support callback targets.",
+                    generator.derive_name()
+                )),
+            }
+            continue;
+        }
+
+        let krate = match load_crate() {
+            Ok(krate) => krate,
+            Err(error) => {
+                impls.push(error);
+                continue;
+            }
+        };
+
+        // Make sure we found the struct at that path or with that name.
+        let resolved = match target {
+            Target::Path(target_path) => {
+                get_struct_from_path(krate.clone(), source, target_path.clone())
+                    .map(|target_struct| (target_path, target_struct))
+            }
+            Target::Name(name) => get_struct_by_name(krate.clone(), &name),
+            Target::Callback(_) => continue,
+        };
+        let (target_path, target_struct) = match resolved {
+            Ok(resolved) => resolved,
+            Err(error) => {
+                impls.push(error);
+                continue;
+            }
+        };
+
+        let params = Parameters {
+            src_struct: source.item.clone(),
+            src_path: source.path.clone(),
+            target_path,
+            target_struct,
+        };
+
+        // Generate the MergeStruct trait implementations.
+        match generate_impl(generator, params, &krate.config.type_aliases) {
+            Ok(ast) => impls.push(ast),
+            Err(error) => {
+                impls.push(error);
+                continue;
+            }
+        }
+    }
+
+    if let Some(Ok(krate)) = &krate {
+        impls.extend(unmatched_field_targets(source, generator, krate));
+    }
+
+    impls
+}
+
+/// Report `#[inter_struct(field = ...)]` attributes, that don't apply to any target.
+///
+/// Targets ignore explicit fields of the wrong kind, i.e. tuple structs ignore `field = "name"`.
+/// As the attribute is shared by all derives of the struct, the targets of the built-in derives
+/// are considered as well. The errors are only reported by the derive whose attribute comes first.
+fn unmatched_field_targets(
+    source: &Source,
+    generator: &dyn Generator,
+    krate: &Rc<Crate>,
+) -> Vec<TokenStream> {
+    let mut names: Vec<&str> = Mode::ALL.iter().map(Mode::attribute_name).collect();
+    names.push(generator.attribute_name());
+    let attributes: Vec<&Attribute> = source
+        .item
+        .attrs
+        .iter()
+        .filter(|attribute| names.iter().any(|name| attribute.path().is_ident(name)))
+        .collect();
+    match attributes.first() {
+        Some(first) if first.path().is_ident(generator.attribute_name()) => (),
+        _ => return Vec::new(),
+    }
+
+    // The kinds of fields the targets have.
+    // Nothing is reported, if any of the targets is unknown, their errors are reported elsewhere.
+    let mut has_named = false;
+    let mut has_unnamed = false;
+    for attribute in attributes {
+        let targets = match attribute.parse_args().map(parse::input_targets) {
+            Ok(Ok(targets)) => targets,
+            _ => return Vec::new(),
+        };
+        for target in targets {
+            let target_struct = match target {
+                Target::Path(path) => get_struct_from_path(krate.clone(), source, path),
+                Target::Name(name) => {
+                    get_struct_by_name(krate.clone(), &name).map(|(_, item)| item)
+                }
+                Target::Callback(_) => return Vec::new(),
+            };
+            match target_struct.map(|item| item.fields) {
+                Ok(Fields::Named(_)) => has_named = true,
+                Ok(Fields::Unnamed(_)) => has_unnamed = true,
+                Ok(Fields::Unit) => (),
+                Err(_) => return Vec::new(),
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    for field in source.item.fields.iter() {
+        let members = match parse::field_targets(field) {
+            Ok(members) => members,
+            Err(_) => continue,
+        };
+        let matches = |member: &Member| match member {
+            Member::Named(_) => has_named,
+            Member::Unnamed(_) => has_unnamed,
+        };
+        if let Some(member) = members.first().filter(|_| !members.iter().any(matches)) {
+            errors.push(err!(
+                member,
+                "None of the target structs has a field '{}'. Named fields are specified by their name, fields of tuple structs by their index.",
+                member.to_token_stream()
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Options of the expansions via [expand_into] and [expand_merge].
+#[derive(Clone, Default)]
+pub struct Options {
+    /// The path to the source struct, as it's used in the generated code.
+    /// Defaults to the name of the struct.
+    pub src_path: Option<Path>,
+    /// The path to the target struct, as it's used in the generated code.
+    /// Defaults to the name of the struct.
+    pub target_path: Option<Path>,
+    /// Use `Default::default` for fields of the target, that don't exist in the source.
+    /// This is the difference between `StructIntoDefault` and `StructInto`.
+    pub default: bool,
+    /// Merge by reference. This is the difference between `StructMergeRef` and `StructMerge`.
+    pub by_ref: bool,
+    /// Type aliases, which are treated like the type they stand for.
+    pub type_aliases: BTreeMap<String, Type>,
+}
+
+/// Generate the `From` implementation from one struct into another.
+///
+/// Both structs are taken as they are, no files are read.
+/// Errors are returned as `compile_error!` invocations.
+pub fn expand_into(src: ItemStruct, target: ItemStruct, options: &Options) -> TokenStream {
+    let mode = match options.default {
+        true => Mode::IntoDefault,
+        false => Mode::Into,
+    };
+
+    expand_with(&mode, src, target, options)
+}
+
+/// Generate the `StructMerge` or `StructMergeRef` implementation of one struct into another.
+///
+/// Both structs are taken as they are, no files are read.
+/// Errors are returned as `compile_error!` invocations.
+pub fn expand_merge(src: ItemStruct, target: ItemStruct, options: &Options) -> TokenStream {
+    let mode = match options.by_ref {
+        true => Mode::MergeRef,
+        false => Mode::Merge,
+    };
+
+    expand_with(&mode, src, target, options)
+}
+
+/// Generate the code of a custom [Generator] for two structs.
+///
+/// Both structs are taken as they are, no files are read.
+/// Only the paths and type aliases of the options are used.
+pub fn expand_with(
+    generator: &dyn Generator,
+    src: ItemStruct,
+    target: ItemStruct,
+    options: &Options,
+) -> TokenStream {
+    let params = Parameters {
+        src_path: options
+            .src_path
+            .clone()
+            .unwrap_or_else(|| Path::from(src.ident.clone())),
+        target_path: options
+            .target_path
+            .clone()
+            .unwrap_or_else(|| Path::from(target.ident.clone())),
+        src_struct: src,
+        target_struct: target,
+    };
+
+    match generate_impl(generator, params, &options.type_aliases) {
+        Ok(tokens) | Err(tokens) => tokens,
+    }
+}
+
+/// Whether the path is relative to the module it's used in.
+fn is_relative(krate: &Crate, path: &Path) -> bool {
+    let first = match path.segments.first() {
+        Some(first) => first.ident.to_string(),
+        None => return false,
+    };
+
+    path.leading_colon.is_none() && first != "crate" && !krate.has_dependency(&first)
+}
+
+/// Return a Tokenstream that contains the implementation for a given trait,
+/// `src` and `target` struct.
+///
+/// Known Limitations:
+/// - Error, when using different generic aliases that have same type.
+/// - Visibility of the `target` struct isn't taken into account.
+///   This might get better when module resolution is done properly.
+/// - Type equality cannot be properly ensured at this stage.
+///   The resulting code will still be correct though, as any type incompatibilities will be
+///   caught by the compiler anyway.
+/// - Type aliases are only known, if they're listed in the configuration.
+pub(crate) fn generate_impl(
+    generator: &dyn Generator,
+    params: Parameters,
+    type_aliases: &BTreeMap<String, Type>,
+) -> Result<TokenStream, TokenStream> {
+    let mapping = StructMapping::new(params, type_aliases)?;
+
+    Ok(generator.generate(&mapping))
+}
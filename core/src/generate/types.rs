@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::punctuated::Pair;
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Expr, ExprPath, GenericParam, Ident, Lifetime, Path, Type, TypePath};
+
+/// A small helper macro, which compares the token streams of two types and enforces their
+/// equality. If they aren't equal, a compiler error will be shown.
+macro_rules! equal_type_or_err {
+    ($src_type:ident, $target_type:ident, $correct_macro:expr) => {
+        if !is_equal_type(&$src_type, &$target_type) {
+            err!(
+                $src_type,
+                "Type '{} cannot be merged into field of type '{}'.",
+                $src_type.to_token_stream(),
+                $target_type.to_token_stream()
+            )
+        } else {
+            $correct_macro
+        }
+    };
+}
+pub(crate) use equal_type_or_err;
+
+/// Check whether two given [Type]s are of the same type.
+/// If they aren't, an error is added to the src_type and the function returns `false`.
+///
+/// This check is rather crude, as we simply compare the token streams of the normalized types.
+/// However, this is the only way for now, as there are no type infos at this stage.
+pub fn is_equal_type(src_type: &Type, target_type: &Type) -> bool {
+    let src_type = normalize_type(src_type.clone());
+    let target_type = normalize_type(target_type.clone());
+    if src_type.to_token_stream().to_string() != target_type.to_token_stream().to_string() {
+        return false;
+    }
+
+    true
+}
+
+/// The types of the prelude by the module they're declared in, i.e. `std::option::Option`.
+const PRELUDE_TYPES: [(&str, &str); 5] = [
+    ("option", "Option"),
+    ("result", "Result"),
+    ("string", "String"),
+    ("vec", "Vec"),
+    ("boxed", "Box"),
+];
+
+/// Bring a type into a canonical form, so equal types consist of the same tokens.
+///
+/// - `Group` and `Paren` wrappers are removed, i.e. `(String)` becomes `String`.
+/// - Paths into `core` and `alloc` are treated like paths into `std`,
+///   i.e. `::core::cell::Cell` becomes `std::cell::Cell`.
+/// - Paths to types of the prelude and to primitive types are shortened to their name,
+///   i.e. `std::option::Option<T>` becomes `Option<T>` and `core::primitive::u8` becomes `u8`.
+///
+/// Other paths are kept as they are, as it's unknown what has been imported.
+pub fn normalize_type(mut ty: Type) -> Type {
+    Normalizer.visit_type_mut(&mut ty);
+
+    ty
+}
+
+/// Normalize a path to a type, see [normalize_type].
+pub fn normalize_path(path: &mut Path) {
+    let is_std = match path.segments.first() {
+        Some(first) => {
+            first.arguments.is_none()
+                && ["std", "core", "alloc"].contains(&first.ident.to_string().as_str())
+        }
+        None => false,
+    };
+    if !is_std || path.segments.len() < 2 {
+        return;
+    }
+
+    path.leading_colon = None;
+    let first = &mut path.segments[0];
+    first.ident = Ident::new("std", first.ident.span());
+
+    let names: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let is_prelude = match names.as_slice() {
+        [_, module, name] => {
+            module == "primitive" || PRELUDE_TYPES.contains(&(module.as_str(), name.as_str()))
+        }
+        _ => false,
+    };
+    if is_prelude {
+        let name = path.segments.pop().map(Pair::into_value);
+        path.segments.clear();
+        path.segments.extend(name);
+    }
+}
+
+/// Remove the `Group` and `Paren` wrappers around a type.
+///
+/// Types are wrapped in invisible groups, if they're passed through a `macro_rules!` macro.
+pub fn unwrap_type(mut ty: Type) -> Type {
+    loop {
+        ty = match ty {
+            Type::Group(group) => *group.elem,
+            Type::Paren(paren) => *paren.elem,
+            ty => return ty,
+        };
+    }
+}
+
+struct Normalizer;
+
+impl VisitMut for Normalizer {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if matches!(ty, Type::Group(_) | Type::Paren(_)) {
+            *ty = unwrap_type(ty.clone());
+        }
+
+        visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        // Parentheses of expressions matter, i.e. in `[u8; (1 + 2) * 3]`.
+        if let Expr::Group(group) = expr {
+            *expr = (*group.expr).clone();
+            self.visit_expr_mut(expr);
+            return;
+        }
+
+        visit_mut::visit_expr_mut(self, expr);
+    }
+
+    fn visit_path_mut(&mut self, path: &mut Path) {
+        visit_mut::visit_path_mut(self, path);
+        normalize_path(path);
+    }
+}
+
+/// Replace all type aliases from the configuration with the types they stand for.
+///
+/// This only affects the comparison of types, as field types never end up in the generated code.
+/// Aliases are only replaced, if they're used as a plain name (i.e. `AlsoString`).
+pub fn expand_type_aliases(ty: &mut Type, aliases: &BTreeMap<String, Type>) {
+    if aliases.is_empty() {
+        return;
+    }
+
+    AliasExpander {
+        aliases,
+        expanding: Vec::new(),
+    }
+    .visit_type_mut(ty);
+}
+
+struct AliasExpander<'a> {
+    aliases: &'a BTreeMap<String, Type>,
+    /// The aliases that are currently being expanded, which prevents endless cycles.
+    expanding: Vec<String>,
+}
+
+impl VisitMut for AliasExpander<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let name = match ty {
+            Type::Path(TypePath { qself: None, path }) => {
+                path.get_ident().map(|ident| ident.to_string())
+            }
+            _ => None,
+        };
+
+        let alias = name
+            .filter(|name| !self.expanding.contains(name))
+            .and_then(|name| self.aliases.get(&name).map(|alias| (name, alias)));
+        match alias {
+            Some((name, alias)) => {
+                // Errors about the expanded type should still point to the field.
+                let tokens = respan(alias.to_token_stream(), ty.span());
+                *ty = syn::parse2(tokens).unwrap_or_else(|_| alias.clone());
+                self.expanding.push(name);
+                self.visit_type_mut(ty);
+                self.expanding.pop();
+            }
+            None => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+}
+
+/// The generic arguments of a target path, by the names of the parameters they replace.
+///
+/// I.e. `crate::api::Page<crate::User>` replaces `T` of `struct Page<T>` with `crate::User`.
+#[derive(Default)]
+pub struct Substitution {
+    pub lifetimes: BTreeMap<String, Lifetime>,
+    pub types: BTreeMap<String, Type>,
+    pub consts: BTreeMap<String, Expr>,
+}
+
+impl Substitution {
+    pub fn is_empty(&self) -> bool {
+        self.lifetimes.is_empty() && self.types.is_empty() && self.consts.is_empty()
+    }
+
+    /// Whether the given generic parameter is replaced.
+    pub fn replaces(&self, param: &GenericParam) -> bool {
+        match param {
+            GenericParam::Lifetime(param) => {
+                self.lifetimes.contains_key(&param.lifetime.to_string())
+            }
+            GenericParam::Type(param) => self.types.contains_key(&param.ident.to_string()),
+            GenericParam::Const(param) => self.consts.contains_key(&param.ident.to_string()),
+        }
+    }
+}
+
+impl VisitMut for Substitution {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let name = match ty {
+            Type::Path(TypePath { qself: None, path }) => path.get_ident().map(ToString::to_string),
+            _ => None,
+        };
+
+        match name {
+            Some(name) if self.types.contains_key(&name) => {
+                let replacement = &self.types[&name];
+                let tokens = respan(replacement.to_token_stream(), ty.span());
+                *ty = syn::parse2(tokens).unwrap_or_else(|_| replacement.clone());
+            }
+            // Const parameters are used like types in generic arguments, i.e. `Limit<N>`.
+            Some(name) if self.consts.contains_key(&name) => {
+                let replacement = self.consts[&name].to_token_stream();
+                *ty = Type::Verbatim(respan(replacement, ty.span()));
+            }
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        let name = match expr {
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) => path.get_ident().map(ToString::to_string),
+            _ => None,
+        };
+
+        match name.and_then(|name| self.consts.get(&name)) {
+            Some(replacement) => *expr = replacement.clone(),
+            None => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if let Some(replacement) = self.lifetimes.get(&lifetime.to_string()) {
+            *lifetime = replacement.clone();
+        }
+    }
+}
+
+/// Set the span of all tokens in the stream.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                return TokenTree::Group(respanned);
+            }
+
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
@@ -0,0 +1,82 @@
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use proc_macro2::{Span, TokenStream};
+
+use crate::config::Config;
+use crate::dependency::{Crate, Workspace};
+use crate::error::*;
+use crate::manifest::{CompileTarget, Manifest};
+
+/// Get the crate that's currently using this proc macro.
+///
+/// The manifest directory is determined via the `CARGO_MANIFEST_DIR` variable, that's always
+/// supplied by cargo and represents the directory containing the `Cargo.toml` for the current
+/// crate. The manifest is then read to find the actual root file of the target that's being
+/// compiled, which might have been configured via `[lib] path` or `[[bin]] path`.
+pub fn get_crate(span: Span) -> Result<Rc<Crate>, TokenStream> {
+    match std::env::var("CARGO_MANIFEST_DIR") {
+        Err(error) => Err(err!(
+            span,
+            "Couldn't read CARGO_MANIFEST_DIR environment variable in InterStruct: {}",
+            error
+        )),
+        Ok(path) => {
+            let path = PathBuf::from(path);
+            if !path.exists() {
+                return Err(err!(
+                    span,
+                    "CARGO_MANIFEST_DIR path doesn't exist in InterStruct: {:?}",
+                    path
+                ));
+            }
+
+            load(&path, &compile_target(), span)
+        }
+    }
+}
+
+/// Load the library of the package in the given directory.
+pub fn load_crate(dir: &Path, span: Span) -> Result<Rc<Crate>, TokenStream> {
+    load(dir, &CompileTarget::Lib, span)
+}
+
+/// Load the given target of the package in the given directory.
+fn load(dir: &Path, target: &CompileTarget, span: Span) -> Result<Rc<Crate>, TokenStream> {
+    let manifest = Manifest::read(dir, span)?;
+    let config = Config::read(dir, span)?;
+    let mut root = manifest.crate_root(target, span)?;
+
+    // The configured root only replaces the root of the library.
+    if let Some(configured) = &config.root {
+        if manifest.lib_root().as_ref() == Some(&root) {
+            root = configured.clone();
+        }
+    }
+
+    let workspace = Rc::new(Workspace::of(&manifest, span)?);
+
+    Ok(Rc::new(Crate {
+        root,
+        manifest,
+        workspace,
+        is_dependency: false,
+        config,
+    }))
+}
+
+/// Determine which target of the package is currently being compiled.
+///
+/// Cargo sets `CARGO_BIN_NAME` while compiling binaries and binary examples.
+/// All other targets (libraries, integration tests, benchmarks) are identified by
+/// `CARGO_CRATE_NAME`.
+fn compile_target() -> CompileTarget {
+    if let Ok(name) = std::env::var("CARGO_BIN_NAME") {
+        return CompileTarget::Bin(name);
+    }
+
+    match std::env::var("CARGO_CRATE_NAME") {
+        Ok(name) => CompileTarget::Crate(name),
+        Err(_) => CompileTarget::Lib,
+    }
+}
@@ -0,0 +1,50 @@
+//! The code generation of inter-struct.
+//!
+//! This crate contains the actual logic of the derive macros in `inter-struct-codegen`.
+//! It's a normal library, which is why it can also be used by build scripts via
+//! `inter-struct-build` or by other tools:
+//!
+//! - [expand_into] and [expand_merge] generate the implementations between two given structs.
+//! - Own derive macros can be written by implementing [Generator], which generates code from the
+//!   [StructMapping] of two structs. It's then used via [derive_with] or [expand_with].
+//! - The [module] resolver finds structs in the files of a crate, which is loaded via
+//!   [load_crate].
+mod cache;
+mod config;
+mod dependency;
+mod error;
+mod generate;
+mod helper;
+mod manifest;
+pub mod module;
+mod parse;
+mod scan;
+
+#[cfg(feature = "debug")]
+#[allow(dead_code)]
+mod debug;
+
+use proc_macro2::TokenStream;
+
+pub use dependency::Crate;
+pub use generate::{
+    derive_with, expand_into, expand_merge, expand_with, FieldMapping, FieldType, Generator, Mode,
+    Options, Source, StructMapping,
+};
+pub use helper::load_crate;
+pub use scan::{expand_crate, Expansion, BUILD_CRATE_VARIABLE};
+
+/// The logic of the derive macro for the given mode.
+pub fn derive(mode: Mode, struct_ast: TokenStream) -> TokenStream {
+    generate::derive(mode, struct_ast)
+}
+
+/// The logic of the `InterStructTarget` derive macro.
+pub fn inter_struct_target(struct_ast: TokenStream) -> TokenStream {
+    generate::callback::target(struct_ast)
+}
+
+/// The logic of the macro, that's called by the macros of target structs in callback mode.
+pub fn callback(input: TokenStream) -> TokenStream {
+    generate::callback::expand(input)
+}
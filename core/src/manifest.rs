@@ -0,0 +1,336 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use toml::{Table, Value};
+
+use crate::cache;
+use crate::error::*;
+
+/// The target of a package that's currently being compiled.
+pub enum CompileTarget {
+    /// The library target of the package.
+    Lib,
+    /// A binary target with the given name.
+    /// Binary examples are compiled as binaries as well.
+    Bin(String),
+    /// Any other target, identified by its crate name.
+    /// This is either the library, an integration test, an example or a benchmark.
+    Crate(String),
+}
+
+impl Display for CompileTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileTarget::Lib => write!(f, "library"),
+            CompileTarget::Bin(name) => write!(f, "binary '{name}'"),
+            CompileTarget::Crate(name) => write!(f, "crate '{name}'"),
+        }
+    }
+}
+
+/// The kinds of targets, whose root files may be auto-discovered by cargo,
+/// alongside the directory they're discovered in.
+const TARGET_KINDS: [(&str, &str); 4] = [
+    ("bin", "src/bin"),
+    ("example", "examples"),
+    ("test", "tests"),
+    ("bench", "benches"),
+];
+
+/// The kinds of dependency tables in a manifest.
+const DEPENDENCY_KINDS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// The parsed `Cargo.toml` of a package.
+///
+/// Only the parts that are relevant for finding the source files of a crate are interpreted.
+#[derive(Clone)]
+pub struct Manifest {
+    /// The directory that contains the `Cargo.toml`.
+    pub dir: PathBuf,
+    table: Table,
+}
+
+impl Manifest {
+    /// Read and parse the `Cargo.toml` in the given directory.
+    pub fn read(dir: &Path, span: Span) -> Result<Manifest, TokenStream> {
+        let table = read_toml(&dir.join("Cargo.toml"), "manifest", span)?;
+
+        Ok(Manifest {
+            dir: dir.to_path_buf(),
+            table,
+        })
+    }
+
+    /// Whether the package has a dependency that's available under the given crate name.
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.dependency_table(name).is_some()
+    }
+
+    /// Get the root file of the library target, if the package has one.
+    ///
+    /// This is either the explicitly configured `[lib] path` or `src/lib.rs`.
+    pub fn lib_root(&self) -> Option<PathBuf> {
+        let configured = self
+            .table
+            .get("lib")
+            .and_then(Value::as_table)
+            .and_then(|lib| lib.get("path"))
+            .and_then(Value::as_str);
+
+        match configured {
+            Some(path) => Some(self.dir.join(path)),
+            None => existing(self.dir.join("src/lib.rs")),
+        }
+    }
+
+    /// Get the root file of the binary target with the given name.
+    ///
+    /// Examples are considered as well, as they're compiled as binaries.
+    pub fn bin_root(&self, name: &str) -> Option<PathBuf> {
+        if let Some(root) = self.target_root("bin", name) {
+            return Some(root);
+        }
+
+        // The default binary has the same name as the package.
+        if self.package_name() == Some(name) {
+            if let Some(main) = existing(self.dir.join("src/main.rs")) {
+                return Some(main);
+            }
+        }
+
+        self.target_root("example", name)
+    }
+
+    /// Get the root file of a non-binary target by its crate name.
+    ///
+    /// The crate name is the name of the target, with all `-` replaced by `_`.
+    pub fn crate_name_root(&self, crate_name: &str) -> Option<PathBuf> {
+        if self.lib_name().as_deref() == Some(crate_name) {
+            return self.lib_root();
+        }
+
+        TARGET_KINDS
+            .iter()
+            .find_map(|(kind, _)| self.target_root(kind, crate_name))
+    }
+
+    /// Get the root file of the given target of this package.
+    ///
+    /// This is the file that `crate::` refers to while compiling that target.
+    pub fn crate_root(&self, target: &CompileTarget, span: Span) -> Result<PathBuf, TokenStream> {
+        let root = match target {
+            CompileTarget::Lib => self.lib_root(),
+            CompileTarget::Bin(name) => self.bin_root(name),
+            // Fall back to the library, in case we couldn't identify the target.
+            CompileTarget::Crate(name) => self.crate_name_root(name).or_else(|| self.lib_root()),
+        };
+
+        match root {
+            Some(root) => Ok(root),
+            None => Err(err!(
+                span,
+                "Couldn't find the root file of the {} target in {:?}",
+                target,
+                self.dir
+            )),
+        }
+    }
+
+    /// Get the root file of a target of the given kind (i.e. `bin` or `test`).
+    ///
+    /// This is either the explicitly configured `path` of the target or one of the locations that
+    /// are used by cargo's target auto-discovery.
+    fn target_root(&self, kind: &str, name: &str) -> Option<PathBuf> {
+        let configured = self
+            .target_table(kind, name)
+            .and_then(|target| target.get("path"))
+            .and_then(Value::as_str);
+        if let Some(path) = configured {
+            return Some(self.dir.join(path));
+        }
+
+        let (_, dir) = TARGET_KINDS.iter().find(|(other, _)| *other == kind)?;
+        let dir = self.dir.join(dir);
+
+        // Auto-discovered targets are named after their file or directory.
+        // As we might only know the crate name, the entries have to be compared one by one.
+        // The directory changes, if a target is added.
+        cache::record(&dir);
+        let entries = std::fs::read_dir(dir).ok()?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let target_name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(target_name) => target_name,
+                None => continue,
+            };
+            if !is_same_name(target_name, name) {
+                continue;
+            }
+
+            if path.is_dir() {
+                if let Some(main) = existing(path.join("main.rs")) {
+                    return Some(main);
+                }
+            } else if path.extension().and_then(|extension| extension.to_str()) == Some("rs") {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// Get the explicitly declared target of the given kind (i.e. `[[bin]]`) and name.
+    fn target_table(&self, kind: &str, name: &str) -> Option<&Table> {
+        self.targets(kind).find(|target| {
+            target
+                .get("name")
+                .and_then(Value::as_str)
+                .map(|target_name| is_same_name(target_name, name))
+                .unwrap_or(false)
+        })
+    }
+
+    /// The crate name of the library target.
+    ///
+    /// This is either the explicitly configured `[lib] name` or the name of the package.
+    pub fn lib_name(&self) -> Option<String> {
+        let configured = self
+            .table
+            .get("lib")
+            .and_then(Value::as_table)
+            .and_then(|lib| lib.get("name"))
+            .and_then(Value::as_str);
+
+        configured
+            .or_else(|| self.package_name())
+            .map(|name| name.replace('-', "_"))
+    }
+
+    /// The name of the package.
+    pub fn package_name(&self) -> Option<&str> {
+        self.package_field("name")
+    }
+
+    /// The version of the package.
+    pub fn package_version(&self) -> Option<&str> {
+        self.package_field("version")
+    }
+
+    /// Get a string field of the `[package]` section.
+    fn package_field(&self, name: &str) -> Option<&str> {
+        self.table
+            .get("package")
+            .and_then(Value::as_table)
+            .and_then(|package| package.get(name))
+            .and_then(Value::as_str)
+    }
+
+    /// Find the declaration of the dependency with the given crate name.
+    /// Returns the name of the dependency as written in the manifest, alongside its table.
+    ///
+    /// Dependencies that only specify a version (`name = "1.0"`) don't have a table.
+    pub fn dependency_table(&self, name: &str) -> Option<(&str, Option<&Table>)> {
+        // Platform specific dependencies are declared in `[target.'cfg(...)'.dependencies]`.
+        let platforms = self
+            .table
+            .get("target")
+            .and_then(Value::as_table)
+            .into_iter()
+            .flat_map(|targets| targets.values())
+            .filter_map(Value::as_table);
+
+        std::iter::once(&self.table)
+            .chain(platforms)
+            .flat_map(|table| {
+                DEPENDENCY_KINDS
+                    .iter()
+                    .filter_map(move |kind| table.get(*kind))
+            })
+            .filter_map(Value::as_table)
+            .flatten()
+            .find(|(key, _)| is_same_name(key, name))
+            .map(|(key, value)| (key.as_str(), value.as_table()))
+    }
+
+    /// Get the declaration of a dependency in the `[workspace.dependencies]` of this manifest.
+    ///
+    /// Dependencies that only specify a version (`name = "1.0"`) don't have a table.
+    pub fn workspace_dependency(&self, key: &str) -> Option<Option<&Table>> {
+        self.table
+            .get("workspace")
+            .and_then(Value::as_table)
+            .and_then(|workspace| workspace.get("dependencies"))
+            .and_then(Value::as_table)
+            .and_then(|dependencies| dependencies.get(key))
+            .map(Value::as_table)
+    }
+
+    /// Find the manifest of the workspace this package belongs to.
+    ///
+    /// That's the closest manifest in this or any parent directory with a `[workspace]` section.
+    pub fn workspace(&self, span: Span) -> Result<Option<Manifest>, TokenStream> {
+        if self.table.contains_key("workspace") {
+            return Ok(Some(self.clone()));
+        }
+
+        for dir in self.dir.ancestors().skip(1) {
+            if !cache::exists(&dir.join("Cargo.toml")) {
+                continue;
+            }
+
+            let manifest = Manifest::read(dir, span)?;
+            if manifest.table.contains_key("workspace") {
+                return Ok(Some(manifest));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Iterate over all target tables of a kind, i.e. `[[bin]]` or `[[test]]`.
+    fn targets<'a>(&'a self, kind: &str) -> impl Iterator<Item = &'a Table> {
+        self.table
+            .get(kind)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_table)
+    }
+}
+
+/// Read and parse a toml file, i.e. a `Cargo.toml` or `Cargo.lock`.
+///
+/// `kind` describes the file in error messages.
+pub fn read_toml(path: &Path, kind: &str, span: Span) -> Result<Table, TokenStream> {
+    cache::record(path);
+
+    let content = ok_or_err_return!(
+        std::fs::read_to_string(path),
+        span,
+        "Failed to read {} {:?}: {}",
+        kind,
+        path
+    );
+
+    let table = ok_or_err_return!(
+        content.parse::<Table>(),
+        span,
+        "Failed to parse {} {:?}: {}",
+        kind,
+        path
+    );
+
+    Ok(table)
+}
+
+/// Check whether a target name matches the given name.
+/// Crate names replace all `-` with `_`, which is why both variants are considered equal.
+pub fn is_same_name(target_name: &str, name: &str) -> bool {
+    target_name == name || target_name.replace('-', "_") == name
+}
+
+/// Return the path, if it exists.
+pub fn existing(path: PathBuf) -> Option<PathBuf> {
+    cache::exists(&path).then_some(path)
+}
@@ -0,0 +1,250 @@
+use std::path::Path;
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprLit, Lit, LitBool, Meta, Token};
+
+use crate::error::*;
+
+/// The result of evaluating the `#[cfg(...)]` attributes of an item.
+pub enum Cfg {
+    /// The item is compiled.
+    Enabled,
+    /// The item is removed by the compiler.
+    Disabled,
+    /// The contained predicate cannot be evaluated with the information that's available to us.
+    Unknown(String),
+}
+
+impl Cfg {
+    /// Combine two results, as if both predicates were wrapped in `all(...)`.
+    fn and(self, other: Cfg) -> Cfg {
+        match (self, other) {
+            (Cfg::Disabled, _) | (_, Cfg::Disabled) => Cfg::Disabled,
+            (Cfg::Unknown(predicate), _) | (_, Cfg::Unknown(predicate)) => Cfg::Unknown(predicate),
+            (Cfg::Enabled, Cfg::Enabled) => Cfg::Enabled,
+        }
+    }
+
+    /// Combine two results, as if both predicates were wrapped in `any(...)`.
+    fn or(self, other: Cfg) -> Cfg {
+        match (self, other) {
+            (Cfg::Enabled, _) | (_, Cfg::Enabled) => Cfg::Enabled,
+            (Cfg::Unknown(predicate), _) | (_, Cfg::Unknown(predicate)) => Cfg::Unknown(predicate),
+            (Cfg::Disabled, Cfg::Disabled) => Cfg::Disabled,
+        }
+    }
+
+    /// Negate the result, as if the predicate was wrapped in `not(...)`.
+    fn not(self) -> Cfg {
+        match self {
+            Cfg::Enabled => Cfg::Disabled,
+            Cfg::Disabled => Cfg::Enabled,
+            unknown => unknown,
+        }
+    }
+}
+
+/// Where the items, whose `#[cfg]` attributes are evaluated, are declared.
+pub struct Location<'a> {
+    /// The file that contains the items.
+    pub file: &'a Path,
+    /// Whether the items belong to a dependency of the crate that's being compiled.
+    /// The forwarded features only apply to the crate that's being compiled.
+    pub in_dependency: bool,
+    /// The features from the `[defaults]` of the configuration, which are used if the features
+    /// haven't been forwarded.
+    pub default_features: Option<&'a [String]>,
+}
+
+/// Evaluate all `#[cfg(...)]` attributes of an item.
+///
+/// Proc macros don't know the configuration of the current compilation.
+/// Cargo only exposes it to build scripts, via `CARGO_FEATURE_*` and `CARGO_CFG_*` environment
+/// variables. These have to be forwarded to the compiler by the build script of the crate via
+/// `cargo:rustc-env`. Without them, every predicate is unknown, except for features that are
+/// listed in the configuration.
+pub fn evaluate(attrs: &[Attribute], location: &Location) -> Cfg {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .map(|attr| match attr.parse_args::<Meta>() {
+            Ok(meta) => evaluate_predicate(&meta, location),
+            // Boolean literals (`#[cfg(false)]`) aren't valid meta items.
+            Err(_) => match attr.parse_args::<LitBool>() {
+                Ok(literal) if literal.value => Cfg::Enabled,
+                Ok(_) => Cfg::Disabled,
+                Err(_) => Cfg::Unknown(attr.meta.to_token_stream().to_string()),
+            },
+        })
+        .fold(Cfg::Enabled, Cfg::and)
+}
+
+/// Select the active item among all items with the same name in a module.
+///
+/// Items that are disabled by their `#[cfg]` attributes are ignored.
+/// If multiple items remain, the predicates that decide between them have to be known.
+///
+/// Returns `Ok(None)` if there's no such item at all.
+pub fn select<'a, T>(
+    candidates: Vec<(&'a T, Vec<Attribute>)>,
+    kind: &str,
+    name: &str,
+    location: Location,
+    span: Span,
+) -> Result<Option<&'a T>, TokenStream> {
+    let count = candidates.len();
+    let mut enabled = Vec::new();
+    let mut unknown = Vec::new();
+    for (candidate, attrs) in candidates {
+        match evaluate(&attrs, &location) {
+            Cfg::Enabled => enabled.push(candidate),
+            Cfg::Disabled => (),
+            Cfg::Unknown(predicate) => unknown.push((candidate, predicate)),
+        }
+    }
+
+    // A single remaining item is the only one that can be active.
+    match (enabled.len(), unknown.len()) {
+        (0, 0) if count > 0 => Err(err!(
+            span,
+            "All declarations of {} '{}' in file {:?} are disabled by their #[cfg] attributes.",
+            kind,
+            name,
+            location.file
+        )),
+        (0, 0) => Ok(None),
+        (1, 0) => Ok(enabled.pop()),
+        (0, 1) => Ok(unknown.pop().map(|(candidate, _)| candidate)),
+        (_, 0) => Err(err!(
+            span,
+            "Found multiple declarations of {} '{}' in file {:?} that are enabled at the same time.",
+            kind,
+            name,
+            location.file
+        )),
+        _ => Err(err!(
+            span,
+            "Couldn't decide which declaration of {} '{}' in file {:?} is active, as '{}' cannot be evaluated. Features and cfg options have to be forwarded to the compiler via 'cargo:rustc-env' in the build script.",
+            kind,
+            name,
+            location.file,
+            unknown[0].1
+        )),
+    }
+}
+
+/// Evaluate a single cfg predicate, i.e. `feature = "pg"` or `all(unix, not(test))`.
+fn evaluate_predicate(meta: &Meta, location: &Location) -> Cfg {
+    let unknown = || Cfg::Unknown(meta.to_token_stream().to_string());
+
+    match meta {
+        Meta::List(list) => {
+            let nested = match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            {
+                Ok(nested) => nested,
+                Err(_) => return unknown(),
+            };
+            let mut results = nested.iter().map(|meta| evaluate_predicate(meta, location));
+
+            if list.path.is_ident("all") {
+                results.fold(Cfg::Enabled, Cfg::and)
+            } else if list.path.is_ident("any") {
+                results.fold(Cfg::Disabled, Cfg::or)
+            } else if list.path.is_ident("not") && nested.len() == 1 {
+                results.next().map(Cfg::not).unwrap_or_else(unknown)
+            } else {
+                unknown()
+            }
+        }
+        Meta::Path(path) => {
+            let name = match path.get_ident() {
+                Some(name) => name.to_string(),
+                None => return unknown(),
+            };
+
+            // These options are never exposed to build scripts.
+            if ["test", "doc", "doctest"].contains(&name.as_str()) {
+                return unknown();
+            }
+
+            match cfg_var(&name) {
+                Some(value) => enabled(value.is_some()),
+                None => unknown(),
+            }
+        }
+        Meta::NameValue(name_value) => {
+            let value = match &name_value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(value),
+                    ..
+                }) => value.value(),
+                _ => return unknown(),
+            };
+            let name = match name_value.path.get_ident() {
+                Some(name) => name.to_string(),
+                None => return unknown(),
+            };
+
+            // Features are exposed via their own variables.
+            if name == "feature" {
+                if location.in_dependency {
+                    return unknown();
+                }
+
+                let feature = format!("CARGO_FEATURE_{}", env_name(&value));
+                return match (environment_available(), location.default_features) {
+                    (true, _) => enabled(std::env::var_os(feature).is_some()),
+                    (false, Some(features)) => enabled(features.contains(&value)),
+                    (false, None) => unknown(),
+                };
+            }
+
+            // Options with multiple values (i.e. `target_family`) are separated by commas.
+            match cfg_var(&name) {
+                Some(values) => enabled(
+                    values
+                        .map(|values| values.split(',').any(|other| other == value))
+                        .unwrap_or(false),
+                ),
+                None => unknown(),
+            }
+        }
+    }
+}
+
+/// Get the value of the `CARGO_CFG_*` variable of a cfg option.
+///
+/// Returns `None` if the configuration hasn't been forwarded at all and `Some(None)` if the
+/// option isn't set.
+fn cfg_var(name: &str) -> Option<Option<String>> {
+    if !environment_available() {
+        return None;
+    }
+
+    Some(std::env::var(format!("CARGO_CFG_{}", env_name(name))).ok())
+}
+
+/// Whether the configuration of the current compilation has been forwarded by a build script.
+///
+/// Cargo always sets some `CARGO_CFG_*` variables for build scripts (i.e. `CARGO_CFG_TARGET_OS`).
+fn environment_available() -> bool {
+    std::env::vars_os().any(|(key, _)| {
+        key.to_str()
+            .map(|key| key.starts_with("CARGO_CFG_"))
+            .unwrap_or(false)
+    })
+}
+
+/// Convert a feature or option name to the format of cargo's environment variables.
+fn env_name(name: &str) -> String {
+    name.to_uppercase().replace('-', "_")
+}
+
+fn enabled(enabled: bool) -> Cfg {
+    match enabled {
+        true => Cfg::Enabled,
+        false => Cfg::Disabled,
+    }
+}
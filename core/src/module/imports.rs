@@ -0,0 +1,96 @@
+use syn::{Ident, ItemUse, UseTree};
+
+/// A single name that's imported into a module via a `use` declaration.
+///
+/// Nested use trees such as `use a::{b, c::*};` are flattened into one import per name.
+pub struct Import {
+    /// The name under which the item is available in the importing module.
+    /// Glob imports don't have a name.
+    pub name: Option<String>,
+    /// The path of the imported item or, for glob imports, the path of the module.
+    pub path: Vec<String>,
+    /// Whether the path starts with a leading `::`, i.e. `use ::other_crate::Item;`.
+    /// Such paths always point to other crates.
+    pub global: bool,
+}
+
+impl Import {
+    /// Whether this import brings the given name into scope.
+    pub fn imports(&self, name: &Ident) -> bool {
+        self.name.as_deref() == Some(name.to_string().as_str())
+    }
+
+    /// Whether this is a glob import, i.e. `use some::module::*;`.
+    pub fn is_glob(&self) -> bool {
+        self.name.is_none()
+    }
+}
+
+/// Collect all imports of the given `use` declaration.
+pub fn use_imports(item_use: &ItemUse) -> Vec<Import> {
+    let mut imports = Vec::new();
+    let global = item_use.leading_colon.is_some();
+    flatten_use_tree(&item_use.tree, Vec::new(), global, &mut imports);
+
+    imports
+}
+
+/// Recursively walk through a use tree and collect all imports.
+fn flatten_use_tree(
+    tree: &UseTree,
+    mut prefix: Vec<String>,
+    global: bool,
+    imports: &mut Vec<Import>,
+) {
+    match tree {
+        UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            flatten_use_tree(&use_path.tree, prefix, global, imports);
+        }
+        UseTree::Name(use_name) => {
+            // `use some::module::{self};` imports the module itself.
+            if use_name.ident == "self" {
+                if let Some(name) = prefix.last().cloned() {
+                    imports.push(Import {
+                        name: Some(name),
+                        path: prefix,
+                        global,
+                    });
+                }
+                return;
+            }
+
+            prefix.push(use_name.ident.to_string());
+            imports.push(Import {
+                name: Some(use_name.ident.to_string()),
+                path: prefix,
+                global,
+            });
+        }
+        UseTree::Rename(use_rename) => {
+            // Underscore imports don't bring a name into scope.
+            if use_rename.rename == "_" {
+                return;
+            }
+
+            if use_rename.ident != "self" {
+                prefix.push(use_rename.ident.to_string());
+            }
+            imports.push(Import {
+                name: Some(use_rename.rename.to_string()),
+                path: prefix,
+                global,
+            });
+        }
+        UseTree::Glob(_) => imports.push(Import {
+            name: None,
+            path: prefix,
+            global,
+        }),
+        UseTree::Group(use_group) => {
+            for tree in use_group.items.iter() {
+                flatten_use_tree(tree, prefix.clone(), global, imports);
+            }
+        }
+    }
+}
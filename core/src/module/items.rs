@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use quote::{quote, ToTokens};
+use syn::parse::Parser;
+use syn::{Attribute, Item};
+
+use super::imports::{use_imports, Import};
+
+/// A span-free summary of an item that's declared in a module.
+///
+/// Syntax trees that are parsed inside of a proc macro contain handles to compiler spans, which
+/// are only valid during a single macro invocation. These summaries only contain what's needed to
+/// resolve modules and structs, which is why they can be cached across invocations.
+pub enum ModuleItem {
+    /// A module declaration. Inline modules contain their items.
+    Mod {
+        name: String,
+        attrs: String,
+        content: Option<Arc<Vec<ModuleItem>>>,
+    },
+    /// A struct declaration alongside its source code.
+    Struct {
+        name: String,
+        attrs: String,
+        source: String,
+    },
+    /// A type alias alongside its source code.
+    TypeAlias {
+        name: String,
+        attrs: String,
+        source: String,
+    },
+    /// All names that are imported by a `use` declaration.
+    Use { attrs: String, imports: Vec<Import> },
+}
+
+impl ModuleItem {
+    /// Summarize all items of a module that are relevant for the resolution of structs.
+    pub fn summarize(items: Vec<Item>) -> Vec<ModuleItem> {
+        items
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::Mod(item_mod) => Some(ModuleItem::Mod {
+                    name: item_mod.ident.to_string(),
+                    attrs: relevant_attributes(&item_mod.attrs),
+                    content: item_mod
+                        .content
+                        .map(|(_, items)| Arc::new(ModuleItem::summarize(items))),
+                }),
+                Item::Struct(item_struct) => Some(ModuleItem::Struct {
+                    name: item_struct.ident.to_string(),
+                    attrs: relevant_attributes(&item_struct.attrs),
+                    source: item_struct.to_token_stream().to_string(),
+                }),
+                Item::Type(item_type) => Some(ModuleItem::TypeAlias {
+                    name: item_type.ident.to_string(),
+                    attrs: relevant_attributes(&item_type.attrs),
+                    source: item_type.to_token_stream().to_string(),
+                }),
+                Item::Use(item_use) => Some(ModuleItem::Use {
+                    attrs: relevant_attributes(&item_use.attrs),
+                    imports: use_imports(&item_use),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Parse the `#[cfg]` and `#[path]` attributes of this item.
+    pub fn attributes(&self) -> Vec<Attribute> {
+        let attrs = match self {
+            ModuleItem::Mod { attrs, .. }
+            | ModuleItem::Struct { attrs, .. }
+            | ModuleItem::TypeAlias { attrs, .. }
+            | ModuleItem::Use { attrs, .. } => attrs,
+        };
+
+        // The attributes have been valid attributes before, so they're valid now.
+        Attribute::parse_outer.parse_str(attrs).unwrap_or_default()
+    }
+}
+
+/// Keep the attributes that are relevant for the resolution of structs.
+fn relevant_attributes(attrs: &[Attribute]) -> String {
+    let attrs = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg") || attr.path().is_ident("path"));
+
+    quote!(#(#attrs)*).to_string()
+}
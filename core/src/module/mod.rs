@@ -0,0 +1,1047 @@
+//! Resolution of structs in the files of a crate.
+//!
+//! Proc macros can't ask the compiler where a struct is declared. Instead, the `mod` and `use`
+//! declarations of the crate are followed, starting at its root file, until the struct is found.
+use std::ops::ControlFlow;
+use std::path::{Path as FilePath, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Attribute, Expr, ExprLit, Ident, ItemStruct,
+    ItemType, Lit, LitStr, Path, Token, Type, TypePath,
+};
+
+use crate::cache::{self, Query, Resolved};
+use crate::config::CONFIG_FILE;
+use crate::dependency::Crate;
+use crate::error::*;
+use crate::generate::{instantiate_alias, Mode, Source};
+
+mod cfg;
+mod imports;
+mod items;
+
+use imports::Import;
+pub(crate) use items::ModuleItem;
+
+/// A module of the crate that's currently being compiled.
+///
+/// Modules can either be backed by their own file or be declared inline via `mod name { ... }`.
+/// In both cases we keep track of the directory in which nested file modules are located.
+#[derive(Clone)]
+struct Module {
+    /// The crate this module belongs to.
+    krate: Rc<Crate>,
+    /// The path of this module, relative to the crate root.
+    path: Vec<Ident>,
+    /// The file this module is declared in.
+    file_path: PathBuf,
+    /// The directory that contains the files of nested modules.
+    dir: PathBuf,
+    /// Whether this module is declared inline via `mod name { ... }`.
+    /// This is relevant for relative `#[path]` attributes of nested modules.
+    inline: bool,
+    /// All items that are declared in this module.
+    items: Arc<Vec<ModuleItem>>,
+    /// The `#[cfg]` attributes of the declarations of this module and all of its parents.
+    cfg: Vec<Attribute>,
+}
+
+impl Module {
+    /// Read and parse a file module.
+    ///
+    /// `dir` is the directory in which nested modules of this file are expected.
+    fn from_file(
+        krate: Rc<Crate>,
+        path: Vec<Ident>,
+        file_path: PathBuf,
+        dir: PathBuf,
+        span: Span,
+    ) -> Result<Module, TokenStream> {
+        let items = cache::parse_file(&file_path, span)?;
+
+        Ok(Module {
+            krate,
+            path,
+            file_path,
+            dir,
+            inline: false,
+            items,
+            cfg: Vec::new(),
+        })
+    }
+
+    /// Find the declaration of the submodule `name` and load it.
+    ///
+    /// Inline modules (`mod name { ... }`) are taken as they are, while declarations without a
+    /// body (`mod name;`) are loaded from either `name.rs` or `name/mod.rs`.
+    ///
+    /// `#[path = "..."]` attributes on the declaration take precedence over those defaults.
+    ///
+    /// Returns `Ok(None)`, if there's no such module declaration.
+    fn declared_submodule(&self, name: &Ident, span: Span) -> Result<Option<Module>, TokenStream> {
+        // Modules might be declared multiple times with different `#[cfg]` attributes.
+        let candidates = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Mod { name: other, .. } if *name == other => {
+                    Some((item, item.attributes()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let item = match cfg::select(
+            candidates,
+            "module",
+            &name.to_string(),
+            self.cfg_location(),
+            span,
+        )? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let content = match item {
+            ModuleItem::Mod { content, .. } => content,
+            _ => return Ok(None),
+        };
+
+        let attrs = item.attributes();
+        let mut module = self.load_submodule(name, content, &attrs, span)?;
+        module.cfg = self.cfg.clone();
+        module
+            .cfg
+            .extend(attrs.into_iter().filter(|attr| attr.path().is_ident("cfg")));
+
+        Ok(Some(module))
+    }
+
+    /// Load the submodule with the given declaration.
+    fn load_submodule(
+        &self,
+        name: &Ident,
+        content: &Option<Arc<Vec<ModuleItem>>>,
+        attrs: &[Attribute],
+        span: Span,
+    ) -> Result<Module, TokenStream> {
+        let mut path = self.path.clone();
+        path.push(name.clone());
+
+        let path_attribute = path_attribute(attrs, span)?;
+
+        // The module is declared inline, its items are located in the same file.
+        // A `#[path]` attribute on an inline module changes the directory of nested modules.
+        if let Some(items) = content {
+            let dir = match path_attribute {
+                Some(path) => self.dir.join(path),
+                None => self.dir.join(name.to_string()),
+            };
+
+            return Ok(Module {
+                krate: self.krate.clone(),
+                path,
+                file_path: self.file_path.clone(),
+                dir,
+                inline: true,
+                items: items.clone(),
+                cfg: Vec::new(),
+            });
+        }
+
+        // The file location has been explicitly specified.
+        //
+        // On file level, the path is relative to the directory of the current file.
+        // Inside of inline modules, it's relative to the directory of the inline module.
+        // The file is then treated like a `mod.rs` file, i.e. nested modules are located in
+        // the same directory.
+        if let Some(path_attribute) = path_attribute {
+            let file_path = if self.inline {
+                self.dir.join(path_attribute)
+            } else {
+                let file_dir = self.file_path.parent().unwrap_or(&self.dir);
+                file_dir.join(path_attribute)
+            };
+            let dir = file_path
+                .parent()
+                .map(FilePath::to_path_buf)
+                .unwrap_or_default();
+
+            return Module::from_file(self.krate.clone(), path, file_path, dir, span);
+        }
+
+        let dir = self.dir.join(name.to_string());
+
+        // The module lives in its own file.
+        // Rust allows both `name.rs` and `name/mod.rs`.
+        let file_path = self.dir.join(format!("{name}.rs"));
+        if cache::exists(&file_path) {
+            return Module::from_file(self.krate.clone(), path, file_path, dir, span);
+        }
+
+        let file_path = dir.join("mod.rs");
+        if cache::exists(&file_path) {
+            return Module::from_file(self.krate.clone(), path, file_path, dir, span);
+        }
+
+        Err(err!(
+            span,
+            "Couldn't find file for module '{}' in directory {:?}",
+            name,
+            self.dir
+        ))
+    }
+
+    /// The location of this module, against which `#[cfg]` attributes are evaluated.
+    fn cfg_location(&self) -> cfg::Location<'_> {
+        cfg::Location {
+            file: &self.file_path,
+            in_dependency: self.krate.is_dependency,
+            default_features: self.krate.config.default_features.as_deref(),
+        }
+    }
+
+    /// Find a struct or type alias with the given name that's declared in this module.
+    ///
+    /// Both might be declared multiple times with different `#[cfg]` attributes, in which case
+    /// the active declaration is returned.
+    fn declared_type(&self, name: &Ident, span: Span) -> Result<Option<&ModuleItem>, TokenStream> {
+        let candidates = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Struct { name: other, .. }
+                | ModuleItem::TypeAlias { name: other, .. }
+                    if *name == other =>
+                {
+                    Some((item, item.attributes()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        cfg::select(
+            candidates,
+            "type",
+            &name.to_string(),
+            self.cfg_location(),
+            span,
+        )
+    }
+
+    /// Find the import of the given name in this module.
+    ///
+    /// A name might be imported multiple times with different `#[cfg]` attributes, in which case
+    /// the active import is returned.
+    fn imported(&self, name: &Ident, span: Span) -> Result<Option<&Import>, TokenStream> {
+        let candidates = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Use { imports, .. } => Some((item.attributes(), imports)),
+                _ => None,
+            })
+            .flat_map(|(attrs, imports)| {
+                imports
+                    .iter()
+                    .filter(|import| import.imports(name))
+                    .map(move |import| (import, attrs.clone()))
+            })
+            .collect();
+
+        cfg::select(
+            candidates,
+            "import",
+            &name.to_string(),
+            self.cfg_location(),
+            span,
+        )
+    }
+
+    /// All glob imports of this module, that aren't disabled by their `#[cfg]` attributes.
+    fn glob_imports(&self) -> impl Iterator<Item = &Import> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Use { imports, .. } => Some((item, imports)),
+                _ => None,
+            })
+            .filter(|(item, _)| {
+                !matches!(
+                    cfg::evaluate(&item.attributes(), &self.cfg_location()),
+                    cfg::Cfg::Disabled
+                )
+            })
+            .flat_map(|(_, imports)| imports.iter().filter(|import| import.is_glob()))
+    }
+}
+
+/// The kind of item that's being looked up in a module.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Namespace {
+    Module,
+    Struct,
+    TypeAlias,
+}
+
+/// Resolves paths to modules and structs inside of a crate.
+///
+/// Besides plain `mod` declarations, the resolver follows `use` declarations.
+/// That way, structs can be found via their re-exports.
+struct Resolver {
+    /// The crate that's currently being compiled.
+    krate: Rc<Crate>,
+    /// The span errors are attached to.
+    span: Span,
+    /// All imports and type aliases that are currently being followed.
+    /// This is used to detect cyclic `use` declarations and type aliases.
+    import_stack: Vec<(PathBuf, Vec<Ident>, Ident, Namespace)>,
+}
+
+impl Resolver {
+    fn new(krate: Rc<Crate>, span: Span) -> Resolver {
+        Resolver {
+            krate,
+            span,
+            import_stack: Vec::new(),
+        }
+    }
+
+    /// Load the root module of the given crate.
+    fn root_module(&self, krate: &Rc<Crate>) -> Result<Module, TokenStream> {
+        // Nested modules of the crate root are located in the same directory as the root file.
+        let root_dir = krate
+            .root
+            .parent()
+            .map(FilePath::to_path_buf)
+            .unwrap_or_default();
+
+        Module::from_file(
+            krate.clone(),
+            Vec::new(),
+            krate.root.clone(),
+            root_dir,
+            self.span,
+        )
+    }
+
+    /// Load the root module of a dependency of the given crate.
+    ///
+    /// Returns `Ok(None)` if the crate doesn't have a dependency with that name.
+    fn extern_crate(&self, krate: &Crate, name: &Ident) -> Result<Option<Module>, TokenStream> {
+        let dependency = match krate.dependency(&name.to_string(), self.span)? {
+            Some(dependency) => Rc::new(dependency),
+            None => return Ok(None),
+        };
+
+        self.root_module(&dependency).map(Some)
+    }
+
+    /// Load the module at the given (canonical) path, relative to the crate root.
+    fn module_at(&mut self, krate: &Rc<Crate>, path: &[Ident]) -> Result<Module, TokenStream> {
+        let mut module = self.root_module(krate)?;
+        for segment in path {
+            module = self.submodule(&module, segment)?;
+        }
+
+        Ok(module)
+    }
+
+    /// Walk through all modules of the crate in declaration order, until `visit` breaks.
+    ///
+    /// Modules that cannot be loaded, e.g. because their files don't exist, are skipped.
+    fn walk_modules(
+        &mut self,
+        mut visit: impl FnMut(&Module) -> ControlFlow<()>,
+    ) -> Result<(), TokenStream> {
+        let mut stack = vec![self.root_module(&self.krate)?];
+        while let Some(module) = stack.pop() {
+            if visit(&module).is_break() {
+                break;
+            }
+
+            let mut names: Vec<&str> = Vec::new();
+            for item in module.items.iter() {
+                match item {
+                    // Modules with `#[cfg]` attributes might be declared multiple times.
+                    ModuleItem::Mod { name, .. } if !names.contains(&name.as_str()) => {
+                        names.push(name)
+                    }
+                    _ => (),
+                }
+            }
+
+            // Push in reverse order, so the modules are visited in declaration order.
+            for name in names.into_iter().rev() {
+                let name = ident(name, self.span);
+                if let Ok(Some(submodule)) = module.declared_submodule(&name, self.span) {
+                    stack.push(submodule);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the module in which the given source struct is declared.
+    ///
+    /// The compiler tells us the file the struct is located in.
+    /// The module tree of the crate is then searched for the module that's declared in that
+    /// file and that contains the struct. If the struct cannot be found (e.g. because it has been
+    /// generated by another macro), the file module itself is used.
+    fn caller_module(&mut self, source: &Source) -> Result<Module, TokenStream> {
+        let src_struct = &source.item;
+        let file = match &source.file {
+            Some(file) => file,
+            None => {
+                return Err(err!(
+                    self.span,
+                    "The compiler didn't provide the source file of '{}'. {}",
+                    src_struct.ident,
+                    "Relative paths require Rust 1.88 or newer, please use a 'crate::' path."
+                ))
+            }
+        };
+        let span = self.span;
+        let file = ok_or_err_return!(
+            std::fs::canonicalize(file),
+            span,
+            "Failed to resolve source file {:?}: {}",
+            file
+        );
+
+        let mut file_module = None;
+        let mut struct_module = None;
+        self.walk_modules(|module| {
+            let is_same_file = std::fs::canonicalize(&module.file_path)
+                .map(|path| path == file)
+                .unwrap_or(false);
+            if !is_same_file {
+                return ControlFlow::Continue(());
+            }
+
+            // The source struct is being compiled, so its `#[cfg]` attributes don't matter.
+            let declares_struct = module.items.iter().any(|item| match item {
+                ModuleItem::Struct { name, .. } => src_struct.ident == name,
+                _ => false,
+            });
+            if declares_struct {
+                struct_module = Some(module.clone());
+                return ControlFlow::Break(());
+            }
+
+            if !module.inline && file_module.is_none() {
+                file_module = Some(module.clone());
+            }
+
+            ControlFlow::Continue(())
+        })?;
+
+        match struct_module.or(file_module) {
+            Some(module) => Ok(module),
+            None => Err(err!(
+                self.span,
+                "Couldn't find the module of file {:?} in the crate at {:?}",
+                file,
+                self.krate.root
+            )),
+        }
+    }
+
+    /// Resolve a path to a module, starting from the given module.
+    ///
+    /// The path may start with `crate`, `self` or `super`.
+    /// Other paths are interpreted relative to the given module. Their first segment may also be
+    /// the name of a dependency of the crate.
+    fn resolve_module(&mut self, base: &Module, path: &[Ident]) -> Result<Module, TokenStream> {
+        let mut segments = path.iter().peekable();
+
+        let mut start = base.path.clone();
+        let mut relative = true;
+        match segments.peek() {
+            Some(first) if *first == "crate" => {
+                segments.next();
+                start.clear();
+                relative = false;
+            }
+            Some(first) if *first == "self" => {
+                segments.next();
+                relative = false;
+            }
+            _ => (),
+        }
+        while segments.next_if(|segment| *segment == "super").is_some() {
+            relative = false;
+            if start.pop().is_none() {
+                return Err(err!(
+                    self.span,
+                    "There are too many leading 'super' keywords in module path '{}'",
+                    path_to_string(path)
+                ));
+            }
+        }
+
+        let mut module = if start == base.path {
+            base.clone()
+        } else {
+            self.module_at(&base.krate, &start)?
+        };
+
+        // Names that aren't declared or imported in the module might be dependencies.
+        if relative {
+            if let Some(first) = segments.next() {
+                module = match self.submodule(&module, first) {
+                    Ok(submodule) => submodule,
+                    Err(error) => match self.extern_crate(&base.krate, first)? {
+                        Some(root) => root,
+                        None => return Err(error),
+                    },
+                };
+            }
+        }
+
+        for segment in segments {
+            module = self.submodule(&module, segment)?;
+        }
+
+        Ok(module)
+    }
+
+    /// Resolve a path that starts with the name of a dependency of the given crate.
+    /// I.e. `::other_crate::some::Struct`.
+    ///
+    /// The rest of the path is then resolved via `resolve`, relative to the dependency's root.
+    fn resolve_global<T>(
+        &mut self,
+        krate: &Crate,
+        path: &[Ident],
+        resolve: fn(&mut Resolver, &Module, &[Ident]) -> Result<T, TokenStream>,
+    ) -> Result<T, TokenStream> {
+        let (name, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return Err(err!(self.span, "Found an empty path.")),
+        };
+
+        let root = match self.extern_crate(krate, name)? {
+            Some(root) => root,
+            None => {
+                return Err(err!(
+                    self.span,
+                    "Couldn't find dependency '{}' in the manifest of package '{}'.",
+                    name,
+                    krate.manifest.package_name().unwrap_or("unknown")
+                ))
+            }
+        };
+
+        // Resolve the rest of the path relative to the root of the dependency.
+        let mut path = vec![Ident::new("self", self.span)];
+        path.extend(rest.iter().cloned());
+        resolve(self, &root, &path)
+    }
+
+    /// Resolve a path to a struct, starting from the given module.
+    fn resolve_struct(&mut self, base: &Module, path: &[Ident]) -> Result<ItemStruct, TokenStream> {
+        let (name, module_path) = match path.split_last() {
+            Some(split) => split,
+            None => return Err(err!(self.span, "Found an empty path.")),
+        };
+
+        let module = self.resolve_module(base, module_path)?;
+        self.find_struct(&module, name)
+    }
+
+    /// Get the submodule `name` of the given module.
+    ///
+    /// This is either a declared module or a module that has been imported via `use`.
+    fn submodule(&mut self, module: &Module, name: &Ident) -> Result<Module, TokenStream> {
+        if let Some(submodule) = module.declared_submodule(name, self.span)? {
+            return Ok(submodule);
+        }
+
+        match self.follow_imports(module, name, Namespace::Module, Resolver::resolve_module)? {
+            Some(submodule) => Ok(submodule),
+            None => Err(err!(
+                self.span,
+                "Couldn't find module '{}' in file {:?}",
+                name,
+                module.file_path
+            )),
+        }
+    }
+
+    /// Find the struct `name` in the given module.
+    ///
+    /// This is either a declared struct, a type alias of a struct or a struct that has been imported
+    /// via `use`.
+    fn find_struct(&mut self, module: &Module, name: &Ident) -> Result<ItemStruct, TokenStream> {
+        match module.declared_type(name, self.span)? {
+            Some(ModuleItem::Struct { source, .. }) => return parse_struct(source, self.span),
+            Some(ModuleItem::TypeAlias { source, .. }) => {
+                return self.follow_type_alias(module, name, source)
+            }
+            _ => (),
+        }
+
+        match self.follow_imports(module, name, Namespace::Struct, Resolver::resolve_struct)? {
+            Some(item_struct) => Ok(item_struct),
+            None => Err(err!(
+                self.span,
+                "Didn't find struct {} in file {:?}. It's neither declared nor imported in that module.",
+                name,
+                module.file_path
+            )),
+        }
+    }
+
+    /// Resolve the struct a type alias in the given module points to.
+    /// I.e. `type Alias = crate::real::Struct;`.
+    ///
+    /// The path of the alias is resolved relative to the module it's declared in.
+    /// Generic arguments of the alias, i.e. `type UserPage = Page<User>;`, are substituted into the
+    /// struct.
+    fn follow_type_alias(
+        &mut self,
+        module: &Module,
+        name: &Ident,
+        source: &str,
+    ) -> Result<ItemStruct, TokenStream> {
+        let alias: ItemType = ok_or_err_return!(
+            syn::parse_str(source),
+            name,
+            "Failed to parse type alias '{}': {}",
+            name
+        );
+        let path = match alias.ty.as_ref() {
+            Type::Path(TypePath { qself: None, path }) => path,
+            _ => {
+                return Err(err!(
+                    self.span,
+                    "Type alias '{}' in file {:?} doesn't point to a struct.",
+                    name,
+                    module.file_path
+                ))
+            }
+        };
+        let segments: Vec<Ident> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.clone())
+            .collect();
+
+        // Detect type aliases that (indirectly) point to themselves.
+        let key = (
+            module.krate.root.clone(),
+            module.path.clone(),
+            name.clone(),
+            Namespace::TypeAlias,
+        );
+        if self.import_stack.contains(&key) {
+            return Err(err!(
+                self.span,
+                "Found a cycle of type aliases while resolving '{}' in file {:?}",
+                name,
+                module.file_path
+            ));
+        }
+
+        self.import_stack.push(key);
+        let result = if path.leading_colon.is_some() {
+            self.resolve_global(&module.krate, &segments, Resolver::resolve_struct)
+        } else {
+            self.resolve_struct(module, &segments)
+        };
+        self.import_stack.pop();
+
+        instantiate_alias(result?, path, &alias.generics)
+    }
+
+    /// Look for `name` in the `use` declarations of the given module and resolve it via `resolve`.
+    ///
+    /// Explicit imports take precedence over glob imports, just like in Rust.
+    /// Errors while searching through glob imports are ignored, as the name might simply not be
+    /// part of the glob imported module.
+    ///
+    /// Returns `Ok(None)` if no import for that name could be found.
+    fn follow_imports<T>(
+        &mut self,
+        module: &Module,
+        name: &Ident,
+        namespace: Namespace,
+        resolve: fn(&mut Resolver, &Module, &[Ident]) -> Result<T, TokenStream>,
+    ) -> Result<Option<T>, TokenStream> {
+        // Detect `use` declarations that (indirectly) import themselves.
+        let key = (
+            module.krate.root.clone(),
+            module.path.clone(),
+            name.clone(),
+            namespace,
+        );
+        if self.import_stack.contains(&key) {
+            return Err(err!(
+                self.span,
+                "Found a cycle of 'use' declarations while resolving '{}' in file {:?}",
+                name,
+                module.file_path
+            ));
+        }
+
+        self.import_stack.push(key);
+        let result = self.follow_imports_inner(module, name, resolve);
+        self.import_stack.pop();
+
+        result
+    }
+
+    fn follow_imports_inner<T>(
+        &mut self,
+        module: &Module,
+        name: &Ident,
+        resolve: fn(&mut Resolver, &Module, &[Ident]) -> Result<T, TokenStream>,
+    ) -> Result<Option<T>, TokenStream> {
+        let span = self.span;
+        let to_path = |import: &Import| -> Vec<Ident> {
+            import
+                .path
+                .iter()
+                .map(|segment| ident(segment, span))
+                .collect()
+        };
+
+        if let Some(import) = module.imported(name, span)? {
+            if import.global {
+                return self
+                    .resolve_global(&module.krate, &to_path(import), resolve)
+                    .map(Some);
+            }
+
+            return resolve(self, module, &to_path(import)).map(Some);
+        }
+
+        for import in module.glob_imports() {
+            let mut path = to_path(import);
+            path.push(name.clone());
+
+            let resolved = if import.global {
+                self.resolve_global(&module.krate, &path, resolve)
+            } else {
+                resolve(self, module, &path)
+            };
+            if let Ok(resolved) = resolved {
+                return Ok(Some(resolved));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Get the value of the `#[path = "..."]` attribute in the given attributes, if there's any.
+fn path_attribute(attrs: &[Attribute], span: Span) -> Result<Option<PathBuf>, TokenStream> {
+    for attr in attrs {
+        if !attr.path().is_ident("path") {
+            continue;
+        }
+
+        let name_value = ok_or_err_return!(
+            attr.meta.require_name_value(),
+            span,
+            "Found malformed #[path] attribute: {}"
+        );
+
+        return match &name_value.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => Ok(Some(PathBuf::from(lit_str.value()))),
+            _ => Err(err!(span, "Expected a string in #[path] attribute.")),
+        };
+    }
+
+    Ok(None)
+}
+
+/// Create an identifier with the given name, which might be a raw identifier such as `r#type`.
+fn ident(name: &str, span: Span) -> Ident {
+    match name.strip_prefix("r#") {
+        Some(name) => Ident::new_raw(name, span),
+        None => Ident::new(name, span),
+    }
+}
+
+/// Parse the cached source code of a struct.
+fn parse_struct(source: &str, span: Span) -> Result<ItemStruct, TokenStream> {
+    let item_struct = ok_or_err_return!(syn::parse_str(source), span, "Failed to parse struct: {}");
+
+    Ok(item_struct)
+}
+
+/// Format a list of path segments as a Rust path.
+fn path_to_string(path: &[Ident]) -> String {
+    path.iter()
+        .map(Ident::to_string)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// This function takes a path to a struct and returns the AST of that struct.
+///
+/// There is no easy way to do module resolution during this stage of the compilation.
+/// We start at the root of the crate and follow the `mod` declarations for each segment of the
+/// path. Modules can be declared in their own files or inline inside of other modules.
+/// If a name isn't declared in a module, its `use` declarations are followed, which allows
+/// targeting structs via their re-exports.
+///
+/// Paths that don't start with `crate::` (i.e. `self::Target`, `super::dto::Target` or just
+/// `Target`) are resolved relative to the module that contains the source struct.
+///
+/// Paths that start with the name of a path dependency (i.e. `other_crate::dto::Target`) are
+/// resolved inside of that crate.
+pub fn get_struct_from_path(
+    krate: Rc<Crate>,
+    source: &Source,
+    path: Path,
+) -> Result<ItemStruct, TokenStream> {
+    let path_span = path.span();
+
+    // Relative paths depend on the location of the source struct.
+    let caller = source
+        .file
+        .clone()
+        .map(|file| (file, source.item.ident.to_string()));
+    let query = Query {
+        crate_root: krate.root.clone(),
+        caller,
+        target: path.to_token_stream().to_string(),
+    };
+
+    let resolved = cache::resolve(query, || {
+        record_manifests(&krate);
+        let item_struct = resolve_path(krate.clone(), source, &path)?;
+
+        Ok(Resolved {
+            path: path.to_token_stream().to_string(),
+            source: item_struct.to_token_stream().to_string(),
+        })
+    })?;
+
+    parse_struct(&resolved.source, path_span)
+}
+
+fn resolve_path(krate: Rc<Crate>, source: &Source, path: &Path) -> Result<ItemStruct, TokenStream> {
+    let path_span = path.span();
+
+    let segments: Vec<Ident> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.clone())
+        .collect();
+
+    let mut resolver = Resolver::new(krate.clone(), path_span);
+
+    // Paths that start with `::` or the name of a dependency point into another crate.
+    let is_dependency = krate.has_dependency(&segments[0].to_string());
+    if path.leading_colon.is_some() || is_dependency {
+        return resolver.resolve_global(&krate, &segments, Resolver::resolve_struct);
+    }
+
+    let base = if segments[0] == "crate" {
+        resolver.root_module(&krate)?
+    } else {
+        resolver.caller_module(source)?
+    };
+
+    resolver.resolve_struct(&base, &segments)
+}
+
+/// Search the whole crate for a struct with the given name.
+///
+/// This only succeeds, if there's exactly one struct with that name.
+/// Returns the full path to that struct alongside its AST.
+pub fn get_struct_by_name(
+    krate: Rc<Crate>,
+    name: &LitStr,
+) -> Result<(Path, ItemStruct), TokenStream> {
+    let query = Query {
+        crate_root: krate.root.clone(),
+        caller: None,
+        target: name.to_token_stream().to_string(),
+    };
+
+    let resolved = cache::resolve(query, || {
+        record_manifests(&krate);
+        find_struct_by_name(krate.clone(), name)
+    })?;
+
+    // Create the path with the span of the name, so errors in the generated code point to it.
+    let path = LitStr::new(&resolved.path, name.span());
+    let path = ok_or_err_return!(
+        path.parse_with(Path::parse_mod_style),
+        name,
+        "Failed to create path to struct '{}': {}",
+        name.value()
+    );
+
+    Ok((path, parse_struct(&resolved.source, name.span())?))
+}
+
+fn find_struct_by_name(krate: Rc<Crate>, name: &LitStr) -> Result<Resolved, TokenStream> {
+    let ident: Ident = ok_or_err_return!(
+        name.parse(),
+        name,
+        "'{}' isn't a valid struct name: {}",
+        name.value()
+    );
+
+    let mut resolver = Resolver::new(krate, name.span());
+    let mut candidates = Vec::new();
+    let mut error = None;
+    resolver.walk_modules(|module| {
+        match module.declared_type(&ident, name.span()) {
+            Ok(Some(ModuleItem::Struct { source, .. })) => {
+                let mut path = vec![Ident::new("crate", name.span())];
+                path.extend(module.path.iter().cloned());
+                path.push(ident.clone());
+                candidates.push(Resolved {
+                    path: path_to_string(&path),
+                    source: source.clone(),
+                });
+            }
+            Ok(_) => (),
+            Err(err) => {
+                error = Some(err);
+                return ControlFlow::Break(());
+            }
+        }
+
+        ControlFlow::Continue(())
+    })?;
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    if candidates.len() > 1 {
+        let paths: Vec<String> = candidates.into_iter().map(|found| found.path).collect();
+        return Err(err!(
+            name,
+            "Found multiple structs named '{}'. Please use one of these paths instead: {}",
+            ident,
+            paths.join(", ")
+        ));
+    }
+
+    match candidates.pop() {
+        Some(found) => Ok(found),
+        None => Err(err!(
+            name,
+            "Couldn't find a struct named '{}' in this crate.",
+            ident
+        )),
+    }
+}
+
+/// Record the manifests, the lock file and the configuration of the crate for the running
+/// resolution.
+///
+/// They're read before the resolution starts, but decide how dependencies are resolved.
+fn record_manifests(krate: &Crate) {
+    cache::record(&krate.manifest.dir.join("Cargo.toml"));
+    cache::record(&krate.workspace.manifest.dir.join("Cargo.toml"));
+    cache::record(&krate.workspace.manifest.dir.join("Cargo.lock"));
+    cache::record(&krate.manifest.dir.join(CONFIG_FILE));
+}
+
+/// A struct of the crate that derives at least one of the macros.
+pub struct DerivedStruct {
+    pub item: ItemStruct,
+    /// All derive macros of this crate, that are used by the struct.
+    pub modes: Vec<Mode>,
+    /// The path of the module that contains the struct, relative to the crate root.
+    pub module_path: Vec<Ident>,
+    /// The file that contains the struct.
+    pub file: PathBuf,
+    /// The `#[cfg]` attributes of the struct and of all modules that contain it.
+    pub cfg: Vec<Attribute>,
+}
+
+/// Search the whole crate for structs that derive one of the macros.
+///
+/// Only modules that are active or whose `#[cfg]` attributes cannot be evaluated are searched.
+/// Structs that are generated by other macros can't be found.
+pub fn derived_structs(krate: Rc<Crate>, span: Span) -> Result<Vec<DerivedStruct>, TokenStream> {
+    record_manifests(&krate);
+
+    let mut resolver = Resolver::new(krate, span);
+    let mut structs = Vec::new();
+    let mut error = None;
+    resolver.walk_modules(|module| {
+        for item in module.items.iter() {
+            let source = match item {
+                ModuleItem::Struct { source, .. } => source,
+                _ => continue,
+            };
+            let item = match parse_struct(source, span) {
+                Ok(item) => item,
+                Err(err) => {
+                    error = Some(err);
+                    return ControlFlow::Break(());
+                }
+            };
+
+            let modes = derived_modes(&item.attrs);
+            if modes.is_empty() {
+                continue;
+            }
+
+            let mut cfg = module.cfg.clone();
+            cfg.extend(
+                item.attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("cfg"))
+                    .cloned(),
+            );
+
+            structs.push(DerivedStruct {
+                item,
+                modes,
+                module_path: module.path.clone(),
+                file: module.file_path.clone(),
+                cfg,
+            });
+        }
+
+        ControlFlow::Continue(())
+    })?;
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    Ok(structs)
+}
+
+/// All modes whose derive macro is listed in one of the `#[derive]` attributes.
+fn derived_modes(attrs: &[Attribute]) -> Vec<Mode> {
+    let derived: Vec<Path> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect();
+
+    Mode::ALL
+        .into_iter()
+        .filter(|mode| {
+            derived.iter().any(|path| {
+                path.segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == mode.derive_name())
+            })
+        })
+        .collect()
+}
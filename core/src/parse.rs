@@ -0,0 +1,217 @@
+use proc_macro2::TokenStream;
+use syn::{
+    Attribute, Expr, ExprAssign, ExprLit, Field, Ident, Index, ItemStruct, Lit, LitStr, Member,
+    Path,
+};
+
+use crate::error::err;
+
+/// Parse the main attribute of the derive macro.
+///
+/// It basically parses all attributes and returns the attribute that matches `name`.
+/// If there's no matching attribute, an appropriate compiler error message is thrown.
+pub fn attribute(
+    src_struct: &ItemStruct,
+    derive_name: &str,
+    name: &str,
+) -> Result<Attribute, TokenStream> {
+    for attribute in src_struct.attrs.iter() {
+        let path = &attribute.path();
+
+        // Make sure we don't have a multi-segment path in our attribute.
+        // It's probably an attribute from a different macro.
+        if path.segments.len() != 1 {
+            continue;
+        }
+
+        let attribute_name = path.segments.first().unwrap().ident.to_string();
+        if name == attribute_name.as_str() {
+            return Ok(attribute.clone());
+        }
+    }
+
+    Err(err!(
+        src_struct.ident,
+        "{} requires the '{}' attribute.",
+        derive_name,
+        name
+    ))
+}
+
+/// A target struct, as it's specified in the attribute of a derive macro.
+pub enum Target {
+    /// The path to the target struct, i.e. `"crate::some_path::Struct"`.
+    Path(Path),
+    /// The name of the target struct, i.e. `name = "Struct"`.
+    /// The whole crate is searched for a struct with this name.
+    Name(LitStr),
+    /// The path to a target struct that derives `InterStructTarget`, i.e.
+    /// `callback = "crate::some_path::Struct"`.
+    /// The target struct generates the implementation, no files are read.
+    Callback(Path),
+}
+
+/// Extract the input targets from the macro arguments.
+///
+/// Both, a single target and an array of targets is supported.
+/// I.e.
+/// - `merge_struct("crate::some_path::Struct")`
+/// - `merge_struct(name = "Struct")`
+/// - `merge_struct(callback = "crate::some::Struct")`
+/// - `merge_struct("crate::some::Generic<crate::Struct>")`
+/// - `merge_struct(["crate::some::Struct", "crate::some_other::Struct", name = "Struct"])`
+pub fn input_targets(args: Expr) -> Result<Vec<Target>, TokenStream> {
+    fn lit_to_path(expr: ExprLit) -> Result<Path, TokenStream> {
+        match expr.lit {
+            // Make sure we got a literal string.
+            Lit::Str(lit_str) => match lit_str.parse::<Path>() {
+                Err(_) => Err(err!(
+                    lit_str,
+                    "Only paths are allowed in inter_struct's attributes."
+                )),
+                Ok(path) => {
+                    // Generic arguments, i.e. `crate::Page<crate::User>`, belong to the struct.
+                    let mut modules = path.segments.iter().rev().skip(1);
+                    if modules.any(|segment| !segment.arguments.is_none()) {
+                        return Err(err!(
+                            lit_str,
+                            "Generic arguments are only allowed on the last segment of the path."
+                        ));
+                    }
+
+                    Ok(path)
+                }
+            },
+            _ => Err(err!(
+                expr,
+                "Only paths are allowed in inter_struct's attribute."
+            )),
+        }
+    }
+
+    /// Parse a `name = "Struct"` or `callback = "path::Struct"` assignment.
+    fn assign_to_target(expr: ExprAssign) -> Result<Target, TokenStream> {
+        let option = match expr.left.as_ref() {
+            Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+            _ => None,
+        };
+
+        match (option.as_deref(), *expr.right) {
+            (
+                Some("name"),
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }),
+            ) => Ok(Target::Name(lit_str)),
+            (Some("name"), right) => Err(err!(right, "The struct name has to be a string.")),
+            (Some("callback"), Expr::Lit(right)) => lit_to_path(right).map(Target::Callback),
+            (Some("callback"), right) => Err(err!(
+                right,
+                "Only paths are allowed in inter_struct's attribute."
+            )),
+            _ => Err(err!(
+                expr.left,
+                "Unknown option. Only 'name = \"Struct\"' and 'callback = \"path::Struct\"' are allowed in inter_struct's attribute."
+            )),
+        }
+    }
+
+    fn expr_to_target(expr: Expr) -> Result<Target, TokenStream> {
+        match expr {
+            Expr::Lit(expr) => lit_to_path(expr).map(Target::Path),
+            Expr::Assign(expr) => assign_to_target(expr),
+            _ => Err(err!(
+                expr,
+                "Only paths are allowed in inter_struct's attribute."
+            )),
+        }
+    }
+
+    match args {
+        // Handle the caes of an array of strings, containing paths.
+        Expr::Array(array) => {
+            let mut targets = vec![];
+            for expr in array.elems {
+                targets.push(expr_to_target(expr)?);
+            }
+            Ok(targets)
+        }
+        // Handle the case of a single string containing a path or a single name.
+        Expr::Lit(_) | Expr::Assign(_) => expr_to_target(args).map(|target| vec![target]),
+        _ => Err(err!(
+            args,
+            "inter_struct's macro parameters should be either a single path {} ",
+            "or a vector of paths as str, such as '[\"crate::your::path\"]'."
+        )),
+    }
+}
+
+/// Extract the target fields of a source field from its `#[inter_struct(...)]` attributes.
+///
+/// A field of a named struct is specified by its name, a field of a tuple struct by its index.
+/// I.e.
+/// - `#[inter_struct(field = "id")]`
+/// - `#[inter_struct(field = 0)]`
+pub fn field_targets(field: &Field) -> Result<Vec<Member>, TokenStream> {
+    let mut members = Vec::new();
+    for attribute in field.attrs.iter() {
+        if !attribute.path().is_ident("inter_struct") {
+            continue;
+        }
+
+        let assign: ExprAssign = match attribute.parse_args() {
+            Ok(assign) => assign,
+            Err(_) => {
+                return Err(err!(
+                    attribute,
+                    "Only 'field = \"name\"' and 'field = 0' are allowed in inter_struct's field attribute."
+                ))
+            }
+        };
+        match assign.left.as_ref() {
+            Expr::Path(path) if path.path.is_ident("field") => (),
+            _ => {
+                return Err(err!(
+                    assign.left,
+                    "Unknown option. Only 'field' is allowed in inter_struct's field attribute."
+                ))
+            }
+        }
+
+        let member = match *assign.right {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => match lit_str.parse::<Ident>() {
+                Ok(ident) => Member::Named(ident),
+                Err(_) => {
+                    return Err(err!(
+                        lit_str,
+                        "'{}' isn't a valid field name.",
+                        lit_str.value()
+                    ))
+                }
+            },
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit_int),
+                ..
+            }) => match lit_int.base10_parse::<u32>() {
+                Ok(index) => Member::Unnamed(Index {
+                    index,
+                    span: lit_int.span(),
+                }),
+                Err(_) => return Err(err!(lit_int, "'{}' isn't a valid field index.", lit_int)),
+            },
+            right => {
+                return Err(err!(
+                    right,
+                    "The target field has to be either a name or an index."
+                ))
+            }
+        };
+        members.push(member);
+    }
+
+    Ok(members)
+}
@@ -0,0 +1,100 @@
+//! Code generation for a whole crate, as it's done by the build script of the crate.
+//!
+//! All structs of the library are searched for derives of the macros. Their implementations are
+//! generated in one go and written to a single file, that's then included by the library.
+//! The derive macros themselves don't do anything while the library is compiled.
+use std::path::PathBuf;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Ident, Path};
+
+use crate::cache;
+use crate::generate::{expand, Source};
+use crate::helper::get_crate;
+use crate::module::derived_structs;
+
+/// The environment variable, that's set by the build script to the crate name of the library.
+pub const BUILD_CRATE_VARIABLE: &str = "INTER_STRUCT_BUILD_CRATE";
+
+/// The generated code of a whole crate.
+pub struct Expansion {
+    /// The implementations of all derives.
+    /// Errors are contained as `compile_error!` invocations.
+    pub tokens: TokenStream,
+    /// The crate name of the library.
+    pub crate_name: Option<String>,
+    /// All files the generated code depends on.
+    pub files: Vec<PathBuf>,
+}
+
+/// Whether the implementations of the crate that's currently being compiled are generated by its
+/// build script.
+///
+/// The build script only covers the library, binaries and tests of the package still use the
+/// derive macros.
+pub fn is_generated_by_build_script() -> bool {
+    let build_crate = match std::env::var(BUILD_CRATE_VARIABLE) {
+        Ok(build_crate) => build_crate,
+        Err(_) => return false,
+    };
+
+    std::env::var("CARGO_BIN_NAME").is_err()
+        && std::env::var("CARGO_CRATE_NAME").is_ok_and(|name| name == build_crate)
+}
+
+/// Generate the implementations of all structs of the library that derive one of the macros.
+///
+/// This has to be called from the build script of the package.
+pub fn expand_crate() -> Expansion {
+    let ((tokens, crate_name), files) = cache::track(|| match expand_crate_inner() {
+        Ok(expansion) => expansion,
+        Err(error) => (error, None),
+    });
+
+    Expansion {
+        tokens,
+        crate_name,
+        files,
+    }
+}
+
+fn expand_crate_inner() -> Result<(TokenStream, Option<String>), TokenStream> {
+    let span = Span::call_site();
+    let krate = get_crate(span)?;
+    let crate_name = krate.manifest.lib_name();
+
+    let mut tokens = TokenStream::new();
+    for derived in derived_structs(krate, span)? {
+        // The generated code is placed in the crate root.
+        let mut path = Path::from(Ident::new("crate", span));
+        for segment in &derived.module_path {
+            path.segments.push(segment.clone().into());
+        }
+        path.segments.push(derived.item.ident.clone().into());
+
+        let source = Source {
+            item: derived.item,
+            path,
+            file: Some(derived.file),
+            in_place: false,
+        };
+
+        let impls: Vec<TokenStream> = derived
+            .modes
+            .into_iter()
+            .flat_map(|mode| expand(mode, &source))
+            .collect();
+
+        // The code only exists, if the struct exists.
+        let cfg = derived.cfg;
+        tokens.extend(quote! {
+            #(#cfg)*
+            const _: () = {
+                #(#impls)*
+            };
+        });
+    }
+
+    Ok((tokens, crate_name))
+}
@@ -0,0 +1,259 @@
+//! Snapshot tests of the generated implementations.
+//!
+//! Run `cargo insta review` after changes to the code generation, to update the snapshots.
+use inter_struct_core::{
+    expand_into, expand_merge, expand_with, FieldType, Generator, Options, StructMapping,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, ItemStruct};
+
+/// Format the generated code, so it can be reviewed in the snapshots.
+fn pretty(tokens: TokenStream) -> String {
+    prettyplease::unparse(&syn::parse2(tokens).expect("generated code isn't a valid file"))
+}
+
+fn source() -> ItemStruct {
+    parse_quote! {
+        pub struct Source {
+            pub normal: String,
+            pub optional: Option<String>,
+            #[cfg(feature = "extra")]
+            pub extra: u32,
+            pub ignored: bool,
+        }
+    }
+}
+
+fn target() -> ItemStruct {
+    parse_quote! {
+        pub struct Target {
+            pub normal: String,
+            pub optional: String,
+            #[cfg(feature = "extra")]
+            pub extra: u32,
+            pub missing: u8,
+        }
+    }
+}
+
+#[test]
+fn into() {
+    let source: ItemStruct = parse_quote! {
+        pub struct Source {
+            pub normal: String,
+            pub optional: Option<String>,
+            pub ignored: bool,
+        }
+    };
+    let target: ItemStruct = parse_quote! {
+        pub struct Target {
+            pub normal: String,
+            pub optional: Option<String>,
+        }
+    };
+
+    insta::assert_snapshot!(pretty(expand_into(source, target, &Options::default())));
+}
+
+#[test]
+fn into_default() {
+    let options = Options {
+        default: true,
+        ..Options::default()
+    };
+
+    insta::assert_snapshot!(pretty(expand_into(source(), target(), &options)));
+}
+
+#[test]
+fn merge() {
+    insta::assert_snapshot!(pretty(expand_merge(
+        source(),
+        target(),
+        &Options::default()
+    )));
+}
+
+#[test]
+fn merge_ref() {
+    let options = Options {
+        by_ref: true,
+        ..Options::default()
+    };
+
+    insta::assert_snapshot!(pretty(expand_merge(source(), target(), &options)));
+}
+
+#[test]
+fn generics() {
+    let source: ItemStruct = parse_quote! {
+        pub struct Page<'a, T: Clone, const N: usize>
+        where
+            T: PartialEq,
+        {
+            pub items: Vec<T>,
+            pub title: Cow<'a, str>,
+            pub total: usize,
+        }
+    };
+    let target: ItemStruct = parse_quote! {
+        pub struct PageDto<'a, 'b: 'a, const N: usize, T: Debug> {
+            pub items: Vec<T>,
+            pub title: Cow<'a, str>,
+            pub total: usize,
+            pub other: &'b str,
+        }
+    };
+    let options = Options {
+        by_ref: true,
+        ..Options::default()
+    };
+
+    insta::assert_snapshot!(pretty(expand_merge(source, target, &options)));
+}
+
+#[test]
+fn tuple_structs() {
+    let source: ItemStruct = parse_quote! {
+        pub struct Point(#[inter_struct(field = "x")] pub i32, pub i32, pub Option<String>);
+    };
+    let tuple: ItemStruct = parse_quote! {
+        pub struct PointDto(pub i32, pub i32, pub Option<String>);
+    };
+    let named: ItemStruct = parse_quote! {
+        pub struct NamedPoint {
+            pub x: i32,
+        }
+    };
+    let unit: ItemStruct = parse_quote!(
+        pub struct Marker;
+    );
+
+    let mut tokens = expand_into(source.clone(), tuple, &Options::default());
+    tokens.extend(expand_into(source.clone(), named, &Options::default()));
+    tokens.extend(expand_merge(source, unit, &Options::default()));
+
+    insta::assert_snapshot!(pretty(tokens));
+}
+
+#[test]
+fn normalized_types() {
+    let source: ItemStruct = parse_quote! {
+        pub struct Source {
+            pub string: alloc::string::String,
+            pub optional: (u32),
+            pub unwrapped: ::std::option::Option<Vec<String>>,
+            pub number: std::primitive::u8,
+        }
+    };
+    let target: ItemStruct = parse_quote! {
+        pub struct Target {
+            pub string: String,
+            pub optional: core::option::Option<u32>,
+            pub unwrapped: alloc::vec::Vec<::std::string::String>,
+            pub number: u8,
+        }
+    };
+
+    insta::assert_snapshot!(pretty(expand_merge(source, target, &Options::default())));
+}
+
+#[test]
+fn paths_and_type_aliases() {
+    let source: ItemStruct = parse_quote! {
+        pub struct Source {
+            pub aliased: AlsoString,
+        }
+    };
+    let target: ItemStruct = parse_quote! {
+        pub struct Target {
+            pub aliased: Option<String>,
+        }
+    };
+    let options = Options {
+        src_path: Some(parse_quote!(crate::dto::Source)),
+        target_path: Some(parse_quote!(other_crate::Target)),
+        type_aliases: [("AlsoString".to_string(), parse_quote!(String))].into(),
+        ..Options::default()
+    };
+
+    insta::assert_snapshot!(pretty(expand_merge(source, target, &options)));
+}
+
+#[test]
+fn incompatible_type() {
+    let source: ItemStruct = parse_quote! {
+        pub struct Source {
+            pub normal: u32,
+        }
+    };
+    let target: ItemStruct = parse_quote! {
+        pub struct Target {
+            pub normal: String,
+        }
+    };
+
+    insta::assert_snapshot!(pretty(expand_into(source, target, &Options::default())));
+}
+
+/// A generator of another crate, which lists the names of all changed fields.
+struct ChangedFields;
+
+impl Generator for ChangedFields {
+    fn derive_name(&self) -> &str {
+        "ChangedFields"
+    }
+
+    fn attribute_name(&self) -> &str {
+        "changed_fields"
+    }
+
+    fn generate(&self, mapping: &StructMapping) -> TokenStream {
+        let checks = mapping.fields.iter().map(|field| {
+            let src = &field.src.ident;
+            let target = &field.target.ident;
+            let name = src.as_ref().map(ToString::to_string);
+            let cfg = &field.cfg;
+
+            match (&field.src_type, &field.target_type) {
+                (FieldType::Optional { .. }, FieldType::Normal(_)) => quote! {
+                    #(#cfg)*
+                    if self.#src.as_ref().is_some_and(|value| *value != target.#target) {
+                        changed.push(#name);
+                    }
+                },
+                _ => quote! {
+                    #(#cfg)*
+                    if self.#src != target.#target {
+                        changed.push(#name);
+                    }
+                },
+            }
+        });
+
+        let src_path = &mapping.src_path;
+        let target_path = &mapping.target_path;
+        let errors = &mapping.errors;
+        quote! {
+            #(#errors)*
+            impl #src_path {
+                pub fn changed_fields(&self, target: &#target_path) -> Vec<&'static str> {
+                    let mut changed = Vec::new();
+                    #(#checks)*
+                    changed
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn custom_generator() {
+    insta::assert_snapshot!(pretty(expand_with(
+        &ChangedFields,
+        source(),
+        target(),
+        &Options::default()
+    )));
+}
@@ -0,0 +1,58 @@
+pub mod dto;
+
+pub use dto::User as ExportedUser;
+
+/// Disabled modules aren't loaded, so their file doesn't even need to exist.
+#[cfg(any())]
+#[path = "missing.rs"]
+pub mod platform;
+
+#[cfg(not(any()))]
+pub mod platform {
+    pub struct Platform {
+        pub name: String,
+    }
+}
+
+/// The features are taken from the `inter-struct.toml`, in which `pg` is disabled.
+#[cfg(feature = "pg")]
+pub use backend::pg::Row;
+#[cfg(not(feature = "pg"))]
+pub use backend::sqlite::Row;
+
+/// `unix` can't be evaluated, as the configuration isn't forwarded.
+#[cfg(unix)]
+pub use backend::pg::Row as Connection;
+#[cfg(not(unix))]
+pub use backend::sqlite::Row as Connection;
+
+pub mod backend {
+    pub mod pg {
+        pub struct Row {
+            pub id: i64,
+            pub schema: String,
+        }
+    }
+
+    pub mod sqlite {
+        pub struct Row {
+            pub id: i64,
+        }
+    }
+}
+
+#[derive(StructInto)]
+#[struct_into("crate::dto::User")]
+pub struct NewUser {
+    pub name: String,
+}
+
+mod inline {
+    #[cfg(feature = "extra")]
+    #[derive(StructMerge, inter_struct::StructMergeRef)]
+    #[struct_merge("crate::dto::User")]
+    #[struct_merge_ref("crate::dto::User")]
+    pub struct Patch {
+        pub name: Option<String>,
+    }
+}
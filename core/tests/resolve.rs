@@ -0,0 +1,130 @@
+//! Tests of the module resolver, which reads the crate in `tests/fixture`.
+use std::path::PathBuf;
+
+use inter_struct_core::module::{derived_structs, get_struct_by_name, get_struct_from_path};
+use inter_struct_core::{load_crate, Mode, Source};
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{parse_quote, ItemStruct, LitStr};
+
+fn fixture() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixture")
+}
+
+fn source() -> Source {
+    let item: ItemStruct = parse_quote! {
+        pub struct NewUser {
+            pub name: String,
+        }
+    };
+
+    Source {
+        path: parse_quote!(NewUser),
+        file: Some(fixture().join("src/lib.rs")),
+        item,
+        in_place: true,
+    }
+}
+
+#[test]
+fn resolve_path() {
+    let krate = load_crate(&fixture(), Span::call_site()).unwrap();
+
+    let user = get_struct_from_path(krate.clone(), &source(), parse_quote!(crate::dto::User));
+    assert_eq!(user.unwrap().ident, "User");
+
+    // Re-exports are followed.
+    let user = get_struct_from_path(krate.clone(), &source(), parse_quote!(crate::ExportedUser));
+    assert_eq!(user.unwrap().ident, "User");
+
+    // Disabled module declarations are skipped, even if their file is missing.
+    let platform = get_struct_from_path(
+        krate.clone(),
+        &source(),
+        parse_quote!(crate::platform::Platform),
+    );
+    assert_eq!(platform.unwrap().ident, "Platform");
+
+    // Only the active one of multiple re-exports with the same name is followed.
+    let row = get_struct_from_path(krate.clone(), &source(), parse_quote!(crate::Row));
+    assert_eq!(row.unwrap().fields.len(), 1);
+
+    // Re-exports, whose predicates can't be evaluated, are reported.
+    let connection =
+        get_struct_from_path(krate.clone(), &source(), parse_quote!(crate::Connection));
+    let error = connection.err().unwrap().to_string();
+    assert!(error.contains("'unix' cannot be evaluated"), "{error}");
+
+    // Paths are relative to the module of the source struct.
+    let user = get_struct_from_path(krate, &source(), parse_quote!(dto::User));
+    assert_eq!(user.unwrap().ident, "User");
+}
+
+#[test]
+fn resolve_name() {
+    let krate = load_crate(&fixture(), Span::call_site()).unwrap();
+
+    let name = LitStr::new("User", Span::call_site());
+    let (path, user) = get_struct_by_name(krate.clone(), &name).unwrap();
+    assert_eq!(path.to_token_stream().to_string(), "crate :: dto :: User");
+    assert_eq!(user.ident, "User");
+
+    let name = LitStr::new("Missing", Span::call_site());
+    assert!(get_struct_by_name(krate, &name).is_err());
+}
+
+#[test]
+fn find_derived_structs() {
+    let krate = load_crate(&fixture(), Span::call_site()).unwrap();
+
+    let structs = derived_structs(krate, Span::call_site()).unwrap();
+    let found: Vec<(String, String, Vec<&str>, usize)> = structs
+        .iter()
+        .map(|derived| {
+            let module = derived
+                .module_path
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            let modes = derived.modes.iter().map(Mode::derive_name).collect();
+            (
+                derived.item.ident.to_string(),
+                module,
+                modes,
+                derived.cfg.len(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            ("NewUser".to_string(), "".to_string(), vec!["StructInto"], 0),
+            (
+                "Patch".to_string(),
+                "inline".to_string(),
+                vec!["StructMerge", "StructMergeRef"],
+                1
+            ),
+        ]
+    );
+}
+
+/// The registry dependency of `testing/registry_fixture` is vendored in a configured directory.
+/// The `vendor` directory of the workspace contains a stray directory of the same name.
+#[test]
+fn resolve_registry_dependency() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../testing/registry_fixture");
+    let krate = load_crate(&dir, Span::call_site()).unwrap();
+    let source = Source {
+        path: parse_quote!(Date),
+        file: Some(dir.join("src/lib.rs")),
+        item: parse_quote!(
+            pub struct Date {}
+        ),
+        in_place: true,
+    };
+
+    let date = get_struct_from_path(krate, &source, parse_quote!(vendored_dep::value::Date));
+    assert_eq!(date.unwrap().fields.len(), 3);
+}
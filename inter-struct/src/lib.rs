@@ -6,6 +6,7 @@
 //! - `StructMergeRef`
 //! - `StructInto`
 //! - `StructDefault`
+//! - `InterStructTarget`
 //!
 //! The general way to use such a derive macro is like this:
 //!
@@ -21,8 +22,22 @@
 //! into some `TargetStruct`.
 //!
 //! Note that the target struct's paths has to be
-//! - contained in this crate.
-//! - relative to the current crate.
+//! - contained in this crate or in one of its path or registry dependencies
+//!   (`other_crate::TargetStruct`).
+//! - either relative to the current crate (`crate::path_to::TargetStruct`) or relative to the
+//!   module of the annotated struct (`self::TargetStruct`, `super::path_to::TargetStruct` or
+//!   just `TargetStruct`). Relative paths require Rust 1.88 or newer.
+//!
+//! Relative paths are resolved just like the compiler does, which includes the `use`
+//! declarations of the module. If `TargetStruct` has already been imported via
+//! `use crate::path_to::TargetStruct;`, `#[struct_into("TargetStruct")]` is sufficient.
+//!
+//! Instead of a path, the name of the target struct can be specified via `name = "TargetStruct"`.
+//! The whole crate is then searched for a struct with that name, which must be unique.
+//!
+//! Target structs that derive `InterStructTarget` can be referenced via
+//! `callback = "crate::path_to::TargetStruct"`. They generate the implementation themselves,
+//! without reading any files.
 //!
 //! Either a single path or a list of paths can be specified.
 //! The traits will then be implemented for each given target struct.
@@ -31,6 +46,8 @@
 //! #[struct_into("crate::path_to::TargetStruct")]
 //! // or
 //! #[struct_into(["crate::path_to::TargetStruct", "crate::path_to::AnotherTargetStruct"])]
+//! // or
+//! #[struct_into(["crate::path_to::TargetStruct", name = "AnotherTargetStruct"])]
 //! ```
 //!
 //! Each derive macro can have their own options, so please check the individual docs for each
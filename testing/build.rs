@@ -0,0 +1,10 @@
+//! InterStruct needs to know the enabled features and cfg options to choose between items with
+//! `#[cfg]` attributes. Cargo only exposes them to build scripts, so they're forwarded here.
+fn main() {
+    for (key, value) in std::env::vars() {
+        if key.starts_with("CARGO_FEATURE_") || key.starts_with("CARGO_CFG_") {
+            println!("cargo:rustc-env={key}={value}");
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
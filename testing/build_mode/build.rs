@@ -0,0 +1,4 @@
+//! The implementations of the library are generated by the build script.
+fn main() {
+    inter_struct_build::Builder::new().generate().unwrap();
+}
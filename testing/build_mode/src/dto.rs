@@ -0,0 +1,22 @@
+use inter_struct::prelude::*;
+
+#[derive(Default)]
+pub struct Target {
+    pub normal: String,
+    pub optional: Option<String>,
+}
+
+/// Only one of these structs exists, the generated code has to follow.
+#[cfg(feature = "variant")]
+#[derive(StructIntoDefault)]
+#[struct_into_default("crate::dto::Target")]
+pub struct CfgStruct {
+    pub normal: String,
+}
+
+#[cfg(not(feature = "variant"))]
+#[derive(StructIntoDefault)]
+#[struct_into_default("crate::dto::Target")]
+pub struct CfgStruct {
+    pub normal: String,
+}
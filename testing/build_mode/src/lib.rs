@@ -0,0 +1,66 @@
+use inter_struct::prelude::*;
+
+pub mod dto;
+
+include!(concat!(env!("OUT_DIR"), "/inter_struct.rs"));
+
+/// This struct is converted into a struct in another module.
+#[derive(StructInto)]
+#[struct_into("crate::dto::Target")]
+pub struct IntoStruct {
+    pub normal: String,
+    pub optional: Option<String>,
+}
+
+/// This struct is merged into a struct, that's found via its name.
+#[derive(StructMerge, StructMergeRef)]
+#[struct_merge(name = "Target")]
+#[struct_merge_ref(name = "Target")]
+pub struct MergeStruct {
+    pub normal: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_build_mode() {
+        let from = IntoStruct {
+            normal: "from".to_string(),
+            optional: None,
+        };
+
+        let into: dto::Target = from.into();
+        assert_eq!(into.normal, "from");
+        assert_eq!(into.optional, None);
+    }
+
+    #[test]
+    fn merge_build_mode() {
+        let mut target = dto::Target {
+            normal: "target".to_string(),
+            optional: None,
+        };
+        let src = MergeStruct {
+            normal: "from".to_string(),
+        };
+
+        target.merge_ref(&src);
+        assert_eq!(target.normal, "from");
+
+        target.normal = "target".to_string();
+        target.merge(src);
+        assert_eq!(target.normal, "from");
+    }
+
+    #[test]
+    fn cfg_build_mode() {
+        let from = dto::CfgStruct {
+            normal: "from".to_string(),
+        };
+
+        let into: dto::Target = from.into();
+        assert_eq!(into.normal, "from");
+    }
+}
@@ -0,0 +1,32 @@
+use inter_struct::prelude::*;
+
+pub mod nested;
+
+/// This struct is converted into a struct that lives in `rust/nested.rs`.
+#[derive(StructInto)]
+#[struct_into("crate::nested::IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+/// This struct is converted into a struct of a dependency, whose path is declared directly.
+#[derive(StructInto)]
+#[struct_into("testing_dependency::dto::User")]
+pub struct DependencyStruct {
+    pub field: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_custom_root() {
+        let from = FromStruct {
+            normal: "from".to_string(),
+        };
+
+        let into: nested::IntoStruct = from.into();
+        assert_eq!(into.normal, "from");
+    }
+}
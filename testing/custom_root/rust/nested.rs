@@ -0,0 +1,3 @@
+pub struct IntoStruct {
+    pub normal: String,
+}
@@ -0,0 +1,20 @@
+use inter_struct::prelude::*;
+
+pub struct User {
+    pub field: String,
+}
+
+pub struct ReExported {
+    pub field: String,
+}
+
+pub struct Imported {
+    pub field: String,
+}
+
+/// A target for the callback mode, which can be used without reading the files of this crate.
+#[derive(InterStructTarget)]
+pub struct CallbackUser {
+    pub field: String,
+    pub optional: Option<String>,
+}
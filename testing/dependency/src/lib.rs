@@ -0,0 +1,5 @@
+//! A crate without any derives, whose structs are used as targets by the `testing` crate.
+
+pub mod dto;
+
+pub use dto::ReExported as RootReExport;
@@ -0,0 +1,29 @@
+//! Examples are compiled as their own crate.
+//! `crate::` therefore refers to this file.
+use inter_struct::prelude::*;
+
+pub struct Target {
+    pub normal: String,
+    pub optional: Option<String>,
+}
+
+#[derive(StructInto)]
+#[struct_into("crate::Target")]
+pub struct Source {
+    pub normal: String,
+    pub optional: Option<String>,
+    /// This field doesn't exist in the target, hence it'll be ignored.
+    pub ignored: String,
+}
+
+fn main() {
+    let source = Source {
+        normal: "source".to_string(),
+        optional: Some("source".to_string()),
+        ignored: "source".to_string(),
+    };
+
+    let target: Target = source.into();
+    assert_eq!(target.normal, "source");
+    assert_eq!(target.optional, Some("source".to_string()));
+}
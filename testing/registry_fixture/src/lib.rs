@@ -0,0 +1,5 @@
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
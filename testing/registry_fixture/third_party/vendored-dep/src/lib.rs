@@ -0,0 +1,11 @@
+pub mod value {
+    pub use crate::datetime::Date;
+}
+
+mod datetime {
+    pub struct Date {
+        pub year: u16,
+        pub month: u8,
+        pub day: u8,
+    }
+}
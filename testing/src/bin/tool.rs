@@ -0,0 +1,24 @@
+//! An additional binary of this package.
+//!
+//! `crate::` refers to this file instead of `lib.rs` while compiling this binary.
+use inter_struct::prelude::*;
+
+pub mod nested {
+    pub struct InBinFile {
+        pub field: String,
+    }
+}
+
+#[derive(StructInto)]
+#[struct_into("crate::nested::InBinFile")]
+pub struct FromStruct {
+    pub field: String,
+}
+
+fn main() {
+    let into: nested::InBinFile = FromStruct {
+        field: "tool".to_string(),
+    }
+    .into();
+    println!("{}", into.field);
+}
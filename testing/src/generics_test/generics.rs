@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use inter_struct::prelude::*;
+
+    use crate::generics_test::*;
+
+    fn page() -> Page<'static, String, 10> {
+        Page {
+            items: vec!["page".to_string()],
+            title: Cow::Borrowed("page"),
+            selected: Some("page".to_string()),
+            limit: Limit,
+        }
+    }
+
+    fn dto() -> PageDto<'static, 10, String> {
+        PageDto {
+            items: Vec::new(),
+            title: Cow::Borrowed("dto"),
+            selected: None,
+            limit: Limit,
+        }
+    }
+
+    /// Test the implementation of [std::convert::Into] between generic structs.
+    #[test]
+    fn into_generic() {
+        let dto: PageDto<'_, 10, String> = page().into();
+        assert_eq!(dto.items, vec!["page".to_string()]);
+        assert_eq!(dto.title, "page");
+        assert_eq!(dto.selected, Some("page".to_string()));
+        assert_eq!(dto.limit, Limit::<10>);
+
+        let default: PageDefault<'_, String, 10> = page().into();
+        assert_eq!(default.title, "page");
+        assert_eq!(default.total, 0);
+    }
+
+    /// Test the merge implementations between generic structs.
+    #[test]
+    fn merge_generic() {
+        let mut dto = dto();
+        dto.merge_ref(&page());
+        assert_eq!(dto.items, vec!["page".to_string()]);
+        assert_eq!(dto.selected, Some("page".to_string()));
+
+        let mut dto = self::dto();
+        dto.merge(page());
+        assert_eq!(dto.title, "page");
+        assert_eq!(dto.selected, Some("page".to_string()));
+    }
+
+    /// Test the implementations for a concrete instantiation of a generic target struct.
+    #[test]
+    fn concrete_instantiation() {
+        let wrapper = || LimitWrapper {
+            value: Limit,
+            values: vec![Limit, Limit],
+            limit: Limit,
+        };
+
+        let into: Wrapper<Limit<4>, 4> = wrapper().into();
+        assert_eq!(into.values.len(), 2);
+
+        let mut target = Wrapper {
+            value: Limit::<4>,
+            values: Vec::new(),
+            limit: Limit,
+        };
+        target.merge(wrapper());
+        assert_eq!(target.values.len(), 2);
+    }
+
+    /// Test the implementations for type aliases of generic target structs.
+    #[test]
+    fn alias_instantiation() {
+        let wrapper = || AliasWrapper {
+            value: Limit,
+            values: vec![Limit],
+            limit: Limit,
+        };
+
+        let into: LimitAlias = wrapper().into();
+        assert_eq!(into.values.len(), 1);
+
+        let mut target: GenericAlias<Limit<4>> = Wrapper {
+            value: Limit,
+            values: Vec::new(),
+            limit: Limit,
+        };
+        target.merge(wrapper());
+        assert_eq!(target.values.len(), 1);
+    }
+
+    /// Generic optional fields are merged by reference into plain fields and vice versa.
+    #[test]
+    fn merge_ref_optional_generic() {
+        let mut plain = Plain {
+            value: "plain".to_string(),
+        };
+        plain.merge_ref(&Optional { value: None });
+        assert_eq!(plain.value, "plain");
+        plain.merge_ref(&Optional {
+            value: Some("optional".to_string()),
+        });
+        assert_eq!(plain.value, "optional");
+
+        let mut optional = Optional::<String> { value: None };
+        optional.merge_ref(&Plain {
+            value: "plain".to_string(),
+        });
+        assert_eq!(optional.value, Some("plain".to_string()));
+
+        let mut other = Optional::<String> { value: None };
+        other.merge_ref(&optional);
+        assert_eq!(other.value, Some("plain".to_string()));
+    }
+}
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use inter_struct::prelude::*;
+
+mod generics;
+
+/// A struct with a lifetime, a type and a const parameter, as well as a where-clause.
+#[derive(StructInto, StructIntoDefault, StructMerge, StructMergeRef)]
+#[struct_into("crate::generics_test::PageDto")]
+#[struct_into_default("crate::generics_test::PageDefault")]
+#[struct_merge("crate::generics_test::PageDto")]
+#[struct_merge_ref("crate::generics_test::PageDto")]
+pub struct Page<'a, T, const N: usize>
+where
+    T: PartialEq,
+{
+    pub items: Vec<T>,
+    pub title: Cow<'a, str>,
+    pub selected: Option<T>,
+    pub limit: Limit<N>,
+}
+
+/// The parameters are declared in another order, they're matched by their name.
+pub struct PageDto<'a, const N: usize, T: PartialEq> {
+    pub items: Vec<T>,
+    pub title: Cow<'a, str>,
+    pub selected: Option<T>,
+    pub limit: Limit<N>,
+}
+
+/// The missing fields are populated by the [Default] implementation, which requires `T: Default`.
+#[derive(Default)]
+pub struct PageDefault<'a, T: PartialEq, const N: usize> {
+    pub items: Vec<T>,
+    pub title: Cow<'a, str>,
+    pub selected: Option<T>,
+    pub limit: Limit<N>,
+    pub total: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limit<const N: usize>;
+
+/// A concrete struct, that's converted into one instantiation of a generic struct.
+#[derive(StructInto, StructMerge)]
+#[struct_into("crate::generics_test::Wrapper<crate::generics_test::Limit<4>, 4>")]
+#[struct_merge("crate::generics_test::Wrapper<crate::generics_test::Limit<4>, 4>")]
+pub struct LimitWrapper {
+    pub value: crate::generics_test::Limit<4>,
+    pub values: Vec<crate::generics_test::Limit<4>>,
+    pub limit: Limit<4>,
+}
+
+pub struct Wrapper<T, const N: usize> {
+    pub value: T,
+    pub values: Vec<T>,
+    pub limit: Limit<N>,
+}
+
+/// The generic arguments of type aliases are substituted into the struct they point to.
+#[derive(StructInto, StructMerge)]
+#[struct_into("crate::generics_test::LimitAlias")]
+#[struct_merge("crate::generics_test::GenericAlias<Limit<4>>")]
+pub struct AliasWrapper {
+    pub value: Limit<4>,
+    pub values: Vec<Limit<4>>,
+    pub limit: Limit<4>,
+}
+
+pub type LimitAlias = Wrapper<Limit<4>, 4>;
+pub type GenericAlias<T> = Wrapper<T, 4>;
+
+/// Generic fields are cloned by `StructMergeRef`, which requires `Clone` for the generic
+/// parameters themselves, not for the options around them.
+#[derive(StructMergeRef)]
+#[struct_merge_ref(["crate::generics_test::Plain", "crate::generics_test::Optional"])]
+pub struct Optional<T> {
+    pub value: Option<T>,
+}
+
+#[derive(StructMergeRef)]
+#[struct_merge_ref("crate::generics_test::Optional")]
+pub struct Plain<T> {
+    pub value: T,
+}
@@ -25,4 +25,50 @@ mod tests {
         assert_eq!(into.normal, "from_non_optional");
         assert_eq!(into.optional, Some("from_non_optional".to_string()));
     }
+
+    /// Test the implementation of [std::convert::Into] generated by inter-struct.
+    /// The target structs are resolved via their callback macros.
+    #[test]
+    fn test_into_callback() {
+        let from = || FromCallbackStruct {
+            normal: "from".to_string(),
+            optional: "from".to_string(),
+        };
+
+        let into = IntoCallbackStruct::from(from());
+        assert_eq!(into.normal, "from");
+        assert_eq!(into.optional, Some("from".to_string()));
+
+        let into = IntoGeneratedStruct::from(from());
+        assert_eq!(into.normal, "from");
+    }
+
+    /// Test the implementation of [std::convert::Into] generated by inter-struct.
+    /// Type aliases from the configuration are considered equal to the type they stand for.
+    #[test]
+    fn test_into_type_alias() {
+        let from = FromTypeAliasStruct {
+            normal: "from".to_string(),
+            optional: Some("from".to_string()),
+        };
+
+        let into = IntoStruct::from(from);
+        assert_eq!(into.normal, "from");
+        assert_eq!(into.optional, Some("from".to_string()));
+    }
+
+    /// Test the implementation of [std::convert::Into] generated by inter-struct.
+    /// Fields of the target, that are disabled via `#[cfg]`, aren't initialized.
+    #[test]
+    fn test_into_cfg() {
+        let from = FromCfgStruct {
+            normal: "from".to_string(),
+            variant: "from".to_string(),
+        };
+
+        let into = IntoCfgStruct::from(from);
+        assert_eq!(into.normal, "from");
+        #[cfg(feature = "variant")]
+        assert_eq!(into.variant, "from");
+    }
 }
@@ -43,6 +43,16 @@ impl FromStructNonOptional {
     }
 }
 
+/// `AlsoString` is declared as an alias of `String` in the `inter-struct.toml` of this crate.
+#[derive(StructInto)]
+#[struct_into("crate::into_test::IntoStruct")]
+pub struct FromTypeAliasStruct {
+    pub normal: AlsoString,
+    pub optional: Option<AlsoString>,
+}
+
+pub type AlsoString = String;
+
 /// A struct with less, but otherwise identical fields.
 pub struct IntoStruct {
     pub normal: String,
@@ -57,3 +67,61 @@ pub struct IntoDefaultStruct {
     pub normal_additional: String,
     pub optional_additional: Option<String>,
 }
+
+#[derive(StructInto)]
+#[struct_into("crate::into_test::IntoCfgStruct")]
+pub struct FromCfgStruct {
+    pub normal: String,
+    pub variant: String,
+}
+
+/// A struct with a field that only exists, if the `variant` feature is enabled.
+pub struct IntoCfgStruct {
+    pub normal: String,
+    #[cfg(feature = "variant")]
+    pub variant: String,
+}
+
+/// All fields that both structs have in common are disabled without the `variant` feature.
+/// The generated implementations must not produce unused variable warnings either way.
+#[derive(StructInto, StructMerge, StructMergeRef)]
+#[struct_into("crate::into_test::IntoCfgOnlyStruct")]
+#[struct_merge("crate::into_test::IntoCfgOnlyStruct")]
+#[struct_merge_ref("crate::into_test::IntoCfgOnlyStruct")]
+pub struct FromCfgOnlyStruct {
+    pub variant: String,
+}
+
+pub struct IntoCfgOnlyStruct {
+    #[cfg(feature = "variant")]
+    pub variant: String,
+}
+
+/// A target struct that's resolved via the macro of its `InterStructTarget` derive.
+#[derive(InterStructTarget)]
+pub struct IntoCallbackStruct {
+    pub normal: String,
+    pub optional: Option<String>,
+}
+
+/// Structs that are generated by other macros can only be used in callback mode.
+macro_rules! generated_struct {
+    ($name:ident) => {
+        #[derive(InterStructTarget)]
+        pub struct $name {
+            pub normal: String,
+        }
+    };
+}
+
+generated_struct!(IntoGeneratedStruct);
+
+#[derive(StructInto)]
+#[struct_into([
+    callback = "crate::into_test::IntoCallbackStruct",
+    callback = "crate::into_test::IntoGeneratedStruct"
+])]
+pub struct FromCallbackStruct {
+    pub normal: String,
+    pub optional: String,
+}
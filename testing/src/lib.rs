@@ -1,6 +1,11 @@
+pub mod generics_test;
 pub mod into_test;
 pub mod merge_test;
 pub mod path;
+pub mod tuple_test;
+pub mod types_test;
+
+pub use self::path::reexport::ReExportedAtRoot as RootReExport;
 
 pub struct RootLevelFile {
     pub field: String,
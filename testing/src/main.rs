@@ -0,0 +1,22 @@
+//! The default binary of this package.
+//!
+//! `crate::` refers to this file instead of `lib.rs` while compiling this binary.
+use inter_struct::prelude::*;
+
+pub struct InMainFile {
+    pub field: String,
+}
+
+#[derive(StructInto)]
+#[struct_into("crate::InMainFile")]
+pub struct FromStruct {
+    pub field: String,
+}
+
+fn main() {
+    let into: InMainFile = FromStruct {
+        field: "main".to_string(),
+    }
+    .into();
+    println!("{}", into.field);
+}
@@ -47,4 +47,45 @@ mod tests {
         assert_eq!(base.optional, Some("mixed".to_string()));
         assert_eq!(base.ignored, "base");
     }
+
+    /// Test the [StructMerge::merge_ref] function for a struct, whose fields are never optional.
+    /// The values are wrapped in `Some` for optional fields of the target.
+    #[test]
+    fn merge_non_optional() {
+        // The base struct that's going to be merged into.
+        let mut base = Base::new();
+
+        let non_optional = NonOptional::new();
+        base.merge_ref(&non_optional);
+        assert_eq!(base.normal, "non_optional");
+        assert_eq!(base.optional, Some("non_optional".to_string()));
+        assert_eq!(base.ignored, "base");
+    }
+
+    /// Test the [StructMerge::merge_ref] function for a target struct with a `#[cfg]` field.
+    #[test]
+    fn merge_cfg() {
+        let mut base = CfgBase::new();
+
+        let cfg = Cfg::new();
+        base.merge_ref(&cfg);
+        assert_eq!(base.normal, "cfg");
+        #[cfg(feature = "variant")]
+        assert_eq!(base.variant, "cfg");
+    }
+
+    /// Test the [StructMerge::merge_ref] function for a target struct in another crate, that's resolved via its
+    /// callback macro.
+    #[test]
+    fn merge_callback() {
+        let mut base = testing_dependency::dto::CallbackUser {
+            field: "base".to_string(),
+            optional: None,
+        };
+
+        let callback = Callback::new();
+        base.merge_ref(&callback);
+        assert_eq!(base.field, "callback");
+        assert_eq!(base.optional, Some("callback".to_string()));
+    }
 }
@@ -76,3 +76,74 @@ impl Mixed {
         }
     }
 }
+
+/// A struct with the same field types as [Base], but none of them is optional.
+#[derive(StructMerge, StructMergeRef, Clone)]
+#[struct_merge("crate::merge_test::Base")]
+#[struct_merge_ref("crate::merge_test::Base")]
+pub struct NonOptional {
+    pub normal: String,
+    pub optional: String,
+}
+
+impl NonOptional {
+    pub fn new() -> Self {
+        NonOptional {
+            normal: "non_optional".to_string(),
+            optional: "non_optional".to_string(),
+        }
+    }
+}
+
+/// A struct with a field that only exists, if the `variant` feature is enabled.
+pub struct CfgBase {
+    pub normal: String,
+    #[cfg(feature = "variant")]
+    pub variant: String,
+}
+
+impl CfgBase {
+    pub fn new() -> Self {
+        CfgBase {
+            normal: "base".to_string(),
+            #[cfg(feature = "variant")]
+            variant: "base".to_string(),
+        }
+    }
+}
+
+/// The `variant` field is only merged, if it exists in [CfgBase].
+#[derive(StructMerge, StructMergeRef, Clone)]
+#[struct_merge("crate::merge_test::CfgBase")]
+#[struct_merge_ref("crate::merge_test::CfgBase")]
+pub struct Cfg {
+    pub normal: String,
+    pub variant: String,
+}
+
+impl Cfg {
+    pub fn new() -> Self {
+        Cfg {
+            normal: "cfg".to_string(),
+            variant: "cfg".to_string(),
+        }
+    }
+}
+
+/// The target struct is located in another crate and resolved via its callback macro.
+#[derive(StructMerge, StructMergeRef, Clone)]
+#[struct_merge(callback = "testing_dependency::dto::CallbackUser")]
+#[struct_merge_ref(callback = "testing_dependency::dto::CallbackUser")]
+pub struct Callback {
+    pub field: String,
+    pub optional: String,
+}
+
+impl Callback {
+    pub fn new() -> Self {
+        Callback {
+            field: "callback".to_string(),
+            optional: "callback".to_string(),
+        }
+    }
+}
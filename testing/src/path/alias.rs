@@ -0,0 +1,13 @@
+//! Type aliases, which point to target structs.
+use super::file;
+
+pub type Alias = crate::path::InModFile;
+
+/// Aliases are resolved relative to the module they're declared in.
+pub type RelativeAlias = file::submod::SubModInNormalFile;
+
+/// Aliases may point to other aliases and to re-exports.
+pub type AliasOfAlias = Alias;
+pub type AliasOfReExport = crate::RootReExport;
+
+pub type DependencyAlias = ::testing_dependency::dto::User;
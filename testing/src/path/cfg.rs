@@ -0,0 +1,41 @@
+//! Targets with multiple declarations, of which only one is active at a time.
+use inter_struct::prelude::*;
+
+#[cfg(feature = "variant")]
+pub struct Row {
+    pub field: String,
+    pub variant: u32,
+}
+
+#[cfg(not(feature = "variant"))]
+pub struct Row {
+    pub field: String,
+}
+
+/// Each variant of the source struct only compiles with the matching variant of `Row`.
+#[cfg(feature = "variant")]
+#[derive(StructInto)]
+#[struct_into("Row")]
+pub struct CfgRowStruct {
+    pub field: String,
+    pub variant: u32,
+}
+
+#[cfg(not(feature = "variant"))]
+#[derive(StructInto)]
+#[struct_into("Row")]
+pub struct CfgRowStruct {
+    pub field: String,
+}
+
+/// Disabled modules aren't loaded, only the active declaration of the module is used.
+#[cfg(any())]
+#[path = "cfg/platform.rs"]
+pub mod platform;
+
+#[cfg(not(any()))]
+pub mod platform {
+    pub struct Platform {
+        pub field: String,
+    }
+}
@@ -0,0 +1,4 @@
+/// This declaration is disabled, it would fail to compile with the source struct.
+pub struct Platform {
+    pub field: u32,
+}
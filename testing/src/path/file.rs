@@ -7,3 +7,12 @@ pub mod submod {
         pub field: String,
     }
 }
+
+/// Inline modules, which declare another file module.
+/// The file is located in `file/inline/nested_file.rs`.
+pub mod inline {
+    pub mod nested_file;
+
+    #[path = "renamed.rs"]
+    pub mod path_attribute;
+}
@@ -0,0 +1,3 @@
+pub struct InFileAfterInlineMod {
+    pub field: String,
+}
@@ -0,0 +1,4 @@
+/// This module is declared via `#[path = "renamed.rs"]` inside an inline module.
+pub struct InPathAttributeFileInInlineMod {
+    pub field: String,
+}
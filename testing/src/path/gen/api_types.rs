@@ -0,0 +1,6 @@
+/// This module is declared via `#[path = "gen/api_types.rs"]`.
+pub struct InPathAttributeFile {
+    pub field: String,
+}
+
+pub mod nested;
@@ -0,0 +1,4 @@
+/// Modules of a `#[path]` file are located next to the file itself.
+pub struct NestedInPathAttributeFile {
+    pub field: String,
+}
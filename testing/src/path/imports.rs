@@ -0,0 +1,30 @@
+//! Target names that are resolved via the `use` declarations of the source struct's module.
+use inter_struct::prelude::*;
+
+use super::file;
+use super::submod::{self as sub, SubModInModFile};
+use crate::path::reexport::Grouped as Imported;
+use ::testing_dependency::dto::Imported as DependencyImported;
+
+#[derive(StructInto)]
+#[struct_into([
+    "SubModInModFile",
+    "sub::nested::NestedSubModInModFile",
+    "file::InNormalFile",
+    "Imported",
+    "DependencyImported",
+])]
+pub struct ImportsTestStruct {
+    pub field: String,
+}
+
+/// Names can also be imported via glob imports.
+pub mod glob {
+    use super::*;
+
+    #[derive(StructInto)]
+    #[struct_into(["file::submod::SubModInNormalFile", "Imported"])]
+    pub struct GlobImportsTestStruct {
+        pub field: String,
+    }
+}
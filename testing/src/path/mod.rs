@@ -1,6 +1,14 @@
 use inter_struct::prelude::*;
 
+pub mod alias;
+pub mod cfg;
 pub mod file;
+pub mod imports;
+pub mod reexport;
+pub mod relative;
+
+#[path = "gen/api_types.rs"]
+pub mod api;
 
 pub struct InModFile {
     pub field: String,
@@ -13,8 +21,122 @@ pub struct TestStruct {
     pub field: String,
 }
 
+/// Ensure that structs in (potentially nested and alternating) inline modules can be found.
+#[derive(StructInto)]
+#[struct_into([
+    "crate::path::submod::SubModInModFile",
+    "crate::path::submod::nested::NestedSubModInModFile",
+    "crate::path::file::submod::SubModInNormalFile",
+    "crate::path::file::inline::nested_file::InFileAfterInlineMod",
+])]
+pub struct InlineModTestStruct {
+    pub field: String,
+}
+
 pub mod submod {
     pub struct SubModInModFile {
         pub field: String,
     }
+
+    pub mod nested {
+        pub struct NestedSubModInModFile {
+            pub field: String,
+        }
+    }
+}
+
+/// Ensure that `#[path]` attributes on module declarations are respected.
+#[derive(StructInto)]
+#[struct_into([
+    "crate::path::api::InPathAttributeFile",
+    "crate::path::api::nested::NestedInPathAttributeFile",
+    "crate::path::file::inline::path_attribute::InPathAttributeFileInInlineMod",
+])]
+pub struct PathAttributeTestStruct {
+    pub field: String,
+}
+
+/// Ensure that `use` declarations are followed to find re-exported structs.
+#[derive(StructInto)]
+#[struct_into([
+    "crate::RootReExport",
+    "crate::path::reexport::ReExported",
+    "crate::path::reexport::Renamed",
+    "crate::path::reexport::Grouped",
+    "crate::path::reexport::GlobReExported",
+    "crate::path::reexport::module_alias::ViaModuleAlias",
+])]
+pub struct ReExportTestStruct {
+    pub field: String,
+}
+
+/// Ensure that structs can be found by their name, if they're unique in the crate.
+#[derive(StructInto)]
+#[struct_into([
+    name = "InFileAfterInlineMod",
+    name = "NestedInPathAttributeFile",
+])]
+pub struct NameTestStruct {
+    pub field: String,
+}
+
+/// Ensure that structs in path dependencies of this crate can be used as targets.
+#[derive(StructInto)]
+#[struct_into([
+    "testing_dependency::dto::User",
+    "::testing_dependency::RootReExport",
+])]
+pub struct DependencyTestStruct {
+    pub field: String,
+}
+
+/// Ensure that type aliases are followed to the struct they point to.
+#[derive(StructInto)]
+#[struct_into([
+    "crate::path::alias::Alias",
+    "crate::path::alias::RelativeAlias",
+    "crate::path::alias::AliasOfReExport",
+    "crate::path::alias::DependencyAlias",
+])]
+pub struct TypeAliasTestStruct {
+    pub field: String,
+}
+
+/// Aliases of aliases are followed as well.
+#[derive(StructInto)]
+#[struct_into("alias::AliasOfAlias")]
+pub struct NestedTypeAliasTestStruct {
+    pub field: String,
+}
+
+/// Ensure that items, which are disabled by `#[cfg]` attributes, are ignored.
+#[derive(StructInto)]
+#[struct_into("crate::path::cfg::platform::Platform")]
+pub struct CfgTestStruct {
+    pub field: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use inter_struct::prelude::*;
+
+    pub struct InTestMod {
+        pub field: String,
+    }
+
+    /// Structs in `#[cfg(test)]` modules can be used as targets as well.
+    #[derive(StructInto)]
+    #[struct_into("crate::path::tests::InTestMod")]
+    pub struct TestModStruct {
+        pub field: String,
+    }
+
+    #[test]
+    fn into_test_mod() {
+        let src = TestModStruct {
+            field: "test".to_string(),
+        };
+        let target: InTestMod = src.into();
+        assert_eq!(target.field, "test");
+    }
 }
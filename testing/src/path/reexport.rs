@@ -0,0 +1,36 @@
+//! Structs in this module are only reachable via their re-exports.
+
+mod inner {
+    pub struct ReExported {
+        pub field: String,
+    }
+
+    pub mod deeper {
+        pub struct Original {
+            pub field: String,
+        }
+
+        pub struct Grouped {
+            pub field: String,
+        }
+
+        pub struct ViaModuleAlias {
+            pub field: String,
+        }
+    }
+
+    pub struct ReExportedAtRoot {
+        pub field: String,
+    }
+}
+
+mod glob_inner {
+    pub struct GlobReExported {
+        pub field: String,
+    }
+}
+
+pub use self::inner::{ReExported, ReExportedAtRoot};
+pub use glob_inner::*;
+pub use inner::deeper as module_alias;
+pub use inner::deeper::{Grouped, Original as Renamed};
@@ -0,0 +1,38 @@
+//! Target paths that are relative to the module of the source struct.
+use inter_struct::prelude::*;
+
+pub struct SameModule {
+    pub field: String,
+}
+
+pub mod dto {
+    pub struct InDtoModule {
+        pub field: String,
+    }
+}
+
+#[derive(StructInto)]
+#[struct_into(["SameModule", "self::dto::InDtoModule"])]
+pub struct RelativeTestStruct {
+    pub field: String,
+}
+
+/// The source struct is declared in an inline module.
+pub mod nested {
+    use inter_struct::prelude::*;
+
+    pub struct InNestedModule {
+        pub field: String,
+    }
+
+    #[derive(StructInto)]
+    #[struct_into([
+        "super::SameModule",
+        "super::dto::InDtoModule",
+        "self::InNestedModule",
+        "super::super::InModFile",
+    ])]
+    pub struct NestedRelativeTestStruct {
+        pub field: String,
+    }
+}
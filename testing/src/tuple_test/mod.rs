@@ -0,0 +1,62 @@
+use inter_struct::prelude::*;
+
+mod tuple;
+
+/// A newtype, whose field is mapped by its index.
+#[derive(StructInto, StructMerge, StructMergeRef)]
+#[struct_into("crate::tuple_test::UserId")]
+#[struct_merge("crate::tuple_test::UserId")]
+#[struct_merge_ref("crate::tuple_test::UserId")]
+pub struct Id(pub u64);
+
+#[derive(Debug, PartialEq)]
+pub struct UserId(pub u64);
+
+/// A positional record, which is mapped to another tuple struct and to a named struct.
+#[derive(StructInto, StructIntoDefault, StructMerge, StructMergeRef)]
+#[struct_into(["crate::tuple_test::PointDto", "crate::tuple_test::NamedPoint"])]
+#[struct_into_default("crate::tuple_test::Point3d")]
+#[struct_merge(["crate::tuple_test::PointDto", "crate::tuple_test::NamedPoint"])]
+#[struct_merge_ref("crate::tuple_test::PointDto")]
+pub struct Point(
+    #[inter_struct(field = "x")] pub i32,
+    #[inter_struct(field = "y")] pub i32,
+    #[inter_struct(field = "label")] pub Option<String>,
+);
+
+#[derive(Debug, PartialEq)]
+pub struct PointDto(pub i32, pub i32, pub Option<String>);
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Point3d(pub i32, pub i32, pub Option<String>, pub i32);
+
+#[derive(Debug, PartialEq)]
+pub struct NamedPoint {
+    pub label: Option<String>,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A named struct, whose fields are mapped to a tuple struct via their index.
+#[derive(StructInto, StructMerge)]
+#[struct_into(["crate::tuple_test::Pair", "crate::tuple_test::Marker"])]
+#[struct_merge("crate::tuple_test::Pair")]
+pub struct Named {
+    #[inter_struct(field = 1)]
+    pub first: String,
+    #[inter_struct(field = 0)]
+    pub second: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Pair(pub String, pub String);
+
+/// A unit struct, which every struct can be converted into.
+#[derive(Debug, PartialEq)]
+pub struct Marker;
+
+/// A unit struct as source.
+#[derive(StructInto, StructMerge)]
+#[struct_into("crate::tuple_test::Marker")]
+#[struct_merge("crate::tuple_test::Marker")]
+pub struct Empty;
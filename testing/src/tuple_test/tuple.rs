@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use inter_struct::prelude::*;
+
+    use crate::tuple_test::*;
+
+    fn point() -> Point {
+        Point(1, 2, Some("point".to_string()))
+    }
+
+    /// Test the implementation of [std::convert::Into] between tuple structs.
+    #[test]
+    fn into_tuple() {
+        let id: UserId = Id(5).into();
+        assert_eq!(id, UserId(5));
+
+        let dto: PointDto = point().into();
+        assert_eq!(dto, PointDto(1, 2, Some("point".to_string())));
+
+        let point_3d: Point3d = point().into();
+        assert_eq!(point_3d, Point3d(1, 2, Some("point".to_string()), 0));
+    }
+
+    /// Test the merge traits between tuple structs.
+    #[test]
+    fn merge_tuple() {
+        let mut id = UserId(1);
+        Id(2).merge_into_ref(&mut id);
+        assert_eq!(id, UserId(2));
+        Id(3).merge_into(&mut id);
+        assert_eq!(id, UserId(3));
+
+        let mut dto = PointDto(0, 0, None);
+        point().merge_into(&mut dto);
+        assert_eq!(dto, PointDto(1, 2, Some("point".to_string())));
+
+        let mut dto = PointDto(0, 0, None);
+        point().merge_into_ref(&mut dto);
+        assert_eq!(dto, PointDto(1, 2, Some("point".to_string())));
+    }
+
+    /// Tuple struct fields are mapped to named fields via their attributes and vice versa.
+    #[test]
+    fn tuple_and_named() {
+        let named: NamedPoint = point().into();
+        assert_eq!(
+            named,
+            NamedPoint {
+                label: Some("point".to_string()),
+                x: 1,
+                y: 2,
+            }
+        );
+
+        let mut named = NamedPoint {
+            label: None,
+            x: 0,
+            y: 0,
+        };
+        point().merge_into(&mut named);
+        assert_eq!(named.x, 1);
+        assert_eq!(named.label, Some("point".to_string()));
+
+        let source = || Named {
+            first: "first".to_string(),
+            second: "second".to_string(),
+        };
+        let pair: Pair = source().into();
+        assert_eq!(pair, Pair("second".to_string(), "first".to_string()));
+
+        let mut pair = Pair(String::new(), String::new());
+        source().merge_into(&mut pair);
+        assert_eq!(pair, Pair("second".to_string(), "first".to_string()));
+    }
+
+    /// Unit structs get trivial conversions.
+    #[test]
+    fn unit() {
+        let named = Named {
+            first: "first".to_string(),
+            second: "second".to_string(),
+        };
+        let marker: Marker = named.into();
+        assert_eq!(marker, Marker);
+
+        let mut marker = Marker;
+        Empty.merge_into(&mut marker);
+        let marker: Marker = Empty.into();
+        assert_eq!(marker, Marker);
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use inter_struct::prelude::*;
+
+mod types;
+
+/// A struct with all kinds of types, that aren't paths.
+#[derive(StructInto, StructMerge, StructMergeRef)]
+#[struct_into("crate::types_test::Types")]
+#[struct_merge("crate::types_test::Types")]
+#[struct_merge_ref("crate::types_test::Types")]
+pub struct Source<'a> {
+    pub reference: &'a str,
+    pub array: [u8; 4],
+    pub tuple: (u32, u32),
+    pub slice: &'a [u32],
+    pub function: fn(u32) -> u32,
+    pub object: &'a dyn Display,
+    pub unit: (),
+}
+
+pub struct Types<'a> {
+    pub reference: &'a str,
+    pub array: [u8; 4],
+    pub tuple: (u32, u32),
+    pub slice: &'a [u32],
+    pub function: fn(u32) -> u32,
+    pub object: &'a dyn Display,
+    pub unit: (),
+}
+
+/// Trait objects can't be cloned, which is why they're only converted.
+#[derive(StructInto)]
+#[struct_into("crate::types_test::Boxed")]
+pub struct BoxedSource {
+    pub callback: Box<dyn Fn() -> u32>,
+}
+
+pub struct Boxed {
+    pub callback: Box<dyn Fn() -> u32>,
+}
+
+/// Optional arrays and tuples are wrapped and unwrapped just like any other type.
+#[derive(StructInto, StructMerge, StructMergeRef)]
+#[struct_into("crate::types_test::OptionalTypes")]
+#[struct_merge(["crate::types_test::OptionalTypes", "crate::types_test::NormalTypes"])]
+#[struct_merge_ref("crate::types_test::NormalTypes")]
+pub struct NormalTypes {
+    pub array: [u8; 32],
+    pub tuple: (String, u32),
+}
+
+/// The same fields as [NormalTypes], but optional.
+#[derive(StructMerge, StructMergeRef)]
+#[struct_merge("crate::types_test::NormalTypes")]
+#[struct_merge_ref("crate::types_test::NormalTypes")]
+pub struct OptionalTypes {
+    pub array: Option<[u8; 32]>,
+    pub tuple: Option<(String, u32)>,
+}
+
+/// Paths into the standard library are compared by the type they point to.
+#[derive(StructInto, StructMerge)]
+#[struct_into(["crate::types_test::StdPaths", callback = "crate::types_test::GeneratedStruct"])]
+#[struct_merge("crate::types_test::StdPaths")]
+pub struct PreludePaths {
+    pub string: String,
+    pub optional: String,
+    pub boxed: Box<u32>,
+    pub cell: core::cell::Cell<u32>,
+    pub number: u32,
+}
+
+pub struct StdPaths {
+    pub string: std::string::String,
+    pub optional: ::core::option::Option<std::string::String>,
+    pub boxed: std::boxed::Box<u32>,
+    pub cell: std::cell::Cell<u32>,
+    pub number: core::primitive::u32,
+}
+
+/// Types that are passed through `macro_rules!` macros are wrapped in invisible groups.
+macro_rules! typed_struct {
+    ($name:ident, $ty:ty) => {
+        #[derive(InterStructTarget)]
+        pub struct $name {
+            pub optional: $ty,
+        }
+    };
+}
+
+typed_struct!(GeneratedStruct, Option<String>);
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use inter_struct::prelude::*;
+
+    use crate::types_test::*;
+
+    fn double(value: u32) -> u32 {
+        value * 2
+    }
+
+    fn source() -> Source<'static> {
+        Source {
+            reference: "source",
+            array: [1, 2, 3, 4],
+            tuple: (1, 2),
+            slice: &[1, 2],
+            function: double,
+            object: &"source",
+            unit: (),
+        }
+    }
+
+    fn target() -> Types<'static> {
+        Types {
+            reference: "target",
+            array: [0; 4],
+            tuple: (0, 0),
+            slice: &[],
+            function: |value| value,
+            object: &"target",
+            unit: (),
+        }
+    }
+
+    fn assert_source(types: Types) {
+        assert_eq!(types.reference, "source");
+        assert_eq!(types.array, [1, 2, 3, 4]);
+        assert_eq!(types.tuple, (1, 2));
+        assert_eq!(types.slice, &[1, 2]);
+        assert_eq!((types.function)(2), 4);
+        assert_eq!(types.object.to_string(), "source");
+    }
+
+    /// References, arrays, tuples, slices, function pointers and trait objects are converted.
+    #[test]
+    fn into_non_path_types() {
+        assert_source(source().into());
+
+        let boxed: Boxed = BoxedSource {
+            callback: Box::new(|| 5),
+        }
+        .into();
+        assert_eq!((boxed.callback)(), 5);
+    }
+
+    /// References, arrays, tuples, slices, function pointers and trait objects are merged.
+    #[test]
+    fn merge_non_path_types() {
+        let mut types = target();
+        source().merge_into(&mut types);
+        assert_source(types);
+
+        let mut types = target();
+        source().merge_into_ref(&mut types);
+        assert_source(types);
+    }
+
+    /// Optional arrays and tuples follow the rules of all other optional types.
+    #[test]
+    fn optional_non_path_types() {
+        let normal = || NormalTypes {
+            array: [1; 32],
+            tuple: ("normal".to_string(), 1),
+        };
+
+        let optional: OptionalTypes = normal().into();
+        assert_eq!(optional.array, Some([1; 32]));
+        assert_eq!(optional.tuple, Some(("normal".to_string(), 1)));
+
+        let mut optional = OptionalTypes {
+            array: None,
+            tuple: None,
+        };
+        normal().merge_into(&mut optional);
+        assert_eq!(optional.array, Some([1; 32]));
+
+        let mut target = normal();
+        let partial = OptionalTypes {
+            array: Some([2; 32]),
+            tuple: None,
+        };
+        partial.merge_into_ref(&mut target);
+        assert_eq!(target.array, [2; 32]);
+        assert_eq!(target.tuple, ("normal".to_string(), 1));
+        partial.merge_into(&mut target);
+        assert_eq!(target.array, [2; 32]);
+
+        let mut other = normal();
+        NormalTypes {
+            array: [3; 32],
+            tuple: ("other".to_string(), 3),
+        }
+        .merge_into_ref(&mut other);
+        assert_eq!(other.tuple, ("other".to_string(), 3));
+    }
+
+    /// Types are compared after their paths into the standard library have been normalized.
+    #[test]
+    fn normalized_paths() {
+        let source = || PreludePaths {
+            string: "string".to_string(),
+            optional: "optional".to_string(),
+            boxed: Box::new(1),
+            cell: std::cell::Cell::new(2),
+            number: 3,
+        };
+
+        let target: StdPaths = source().into();
+        assert_eq!(target.string, "string");
+        assert_eq!(target.optional, Some("optional".to_string()));
+        assert_eq!(*target.boxed, 1);
+        assert_eq!(target.cell.get(), 2);
+        assert_eq!(target.number, 3);
+
+        let mut target = StdPaths {
+            string: String::new(),
+            optional: None,
+            boxed: Box::new(0),
+            cell: std::cell::Cell::new(0),
+            number: 0,
+        };
+        source().merge_into(&mut target);
+        assert_eq!(target.optional, Some("optional".to_string()));
+        assert_eq!(target.number, 3);
+
+        let generated: GeneratedStruct = source().into();
+        assert_eq!(generated.optional, Some("optional".to_string()));
+    }
+}
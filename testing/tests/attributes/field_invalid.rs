@@ -0,0 +1,13 @@
+use inter_struct::prelude::*;
+
+/// This should crash, since the target field has to be a name or an index.
+#[derive(StructMerge)]
+#[struct_merge("crate::Tuple")]
+pub struct Named {
+    #[inter_struct(field = first)]
+    pub first: u64,
+}
+
+pub struct Tuple(pub u64);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use inter_struct::prelude::*;
+
+/// This should crash, since both fields are mapped to the same target field.
+#[derive(StructMerge)]
+#[struct_merge("crate::Tuple")]
+pub struct Named {
+    #[inter_struct(field = 0)]
+    pub first: u64,
+    #[inter_struct(field = 0)]
+    pub second: u64,
+}
+
+pub struct Tuple(pub u64);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use inter_struct::prelude::*;
+
+/// This should crash, since the target struct doesn't have the given field.
+#[derive(StructInto)]
+#[struct_into("crate::Named")]
+pub struct Tuple(#[inter_struct(field = "id")] pub u64);
+
+pub struct Named {
+    pub identifier: u64,
+}
+
+fn main() {}
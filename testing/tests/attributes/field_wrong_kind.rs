@@ -0,0 +1,23 @@
+use inter_struct::prelude::*;
+
+/// This should crash, since tuple structs don't have named fields.
+#[derive(StructInto)]
+#[struct_into("crate::Pair")]
+pub struct Named {
+    #[inter_struct(field = "first")]
+    pub value: u64,
+}
+
+/// This should crash, since named structs don't have indexed fields.
+#[derive(StructInto, StructMerge)]
+#[struct_into("crate::Id")]
+#[struct_merge("crate::Id")]
+pub struct Tuple(#[inter_struct(field = 0)] pub u64);
+
+pub struct Pair(pub u64);
+
+pub struct Id {
+    pub id: u64,
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+//! Integration tests are compiled as their own crate.
+//! `crate::` therefore refers to this file.
+use inter_struct::prelude::*;
+
+pub struct IntoStruct {
+    pub normal: String,
+    pub optional: Option<String>,
+}
+
+pub mod nested {
+    pub struct MergeStruct {
+        pub normal: String,
+    }
+}
+
+/// Targets in callback mode don't need to be found in this file.
+#[derive(InterStructTarget)]
+pub struct CallbackStruct {
+    pub normal: String,
+}
+
+#[derive(StructInto, StructMerge)]
+#[struct_into("crate::IntoStruct")]
+#[struct_merge(["crate::nested::MergeStruct", callback = "crate::CallbackStruct"])]
+pub struct FromStruct {
+    pub normal: String,
+    pub optional: String,
+}
+
+impl FromStruct {
+    fn new() -> Self {
+        FromStruct {
+            normal: "from".to_string(),
+            optional: "from".to_string(),
+        }
+    }
+}
+
+#[test]
+fn into_in_integration_test() {
+    let into: IntoStruct = FromStruct::new().into();
+    assert_eq!(into.normal, "from");
+    assert_eq!(into.optional, Some("from".to_string()));
+}
+
+#[test]
+fn merge_in_integration_test() {
+    let mut target = nested::MergeStruct {
+        normal: "target".to_string(),
+    };
+    target.merge(FromStruct::new());
+    assert_eq!(target.normal, "from");
+}
+
+#[test]
+fn callback_in_integration_test() {
+    let mut target = CallbackStruct {
+        normal: "target".to_string(),
+    };
+    target.merge(FromStruct::new());
+    assert_eq!(target.normal, "from");
+}
@@ -0,0 +1,15 @@
+use inter_struct::prelude::*;
+
+/// This should crash, since the types of the callback target are incompatible.
+#[derive(StructInto)]
+#[struct_into(callback = "crate::IntoStruct")]
+pub struct FromStruct {
+    pub normal: i32,
+}
+
+#[derive(InterStructTarget)]
+pub struct IntoStruct {
+    pub normal: String,
+}
+
+fn main() {}
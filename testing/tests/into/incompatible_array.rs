@@ -0,0 +1,16 @@
+use inter_struct::prelude::*;
+
+/// This should crash, since the arrays have different lengths.
+#[derive(StructInto)]
+#[struct_into("crate::IntoStruct")]
+pub struct FromStruct {
+    pub array: [u8; 16],
+    pub optional: Option<(u32, u32)>,
+}
+
+pub struct IntoStruct {
+    pub array: [u8; 32],
+    pub optional: Option<(u32, u64)>,
+}
+
+fn main() {}
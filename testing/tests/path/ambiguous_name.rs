@@ -0,0 +1,22 @@
+use inter_struct::prelude::*;
+
+mod first {
+    pub struct IntoStruct {
+        pub normal: String,
+    }
+}
+
+mod second {
+    pub struct IntoStruct {
+        pub normal: String,
+    }
+}
+
+/// This shouldn't compile, as there are multiple structs with that name.
+#[derive(StructInto)]
+#[struct_into(name = "IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
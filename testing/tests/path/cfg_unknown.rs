@@ -0,0 +1,20 @@
+use inter_struct::prelude::*;
+
+#[cfg(test)]
+pub struct IntoStruct {
+    pub normal: String,
+}
+
+#[cfg(not(test))]
+pub struct IntoStruct {
+    pub normal: String,
+}
+
+/// This shouldn't compile, as proc macros cannot know whether `test` is enabled.
+#[derive(StructInto)]
+#[struct_into("crate::IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
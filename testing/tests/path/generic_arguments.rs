@@ -0,0 +1,21 @@
+use inter_struct::prelude::*;
+
+pub struct Page<T> {
+    pub items: Vec<T>,
+}
+
+/// This shouldn't compile, as `Page` only has a single generic parameter.
+#[derive(StructInto)]
+#[struct_into("crate::Page<String, u32>")]
+pub struct FromStruct {
+    pub items: Vec<String>,
+}
+
+/// This shouldn't compile, as generic arguments of modules don't exist.
+#[derive(StructMerge)]
+#[struct_merge("crate::module<u32>::Page<String>")]
+pub struct MergeStruct {
+    pub items: Vec<String>,
+}
+
+fn main() {}
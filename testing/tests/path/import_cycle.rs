@@ -0,0 +1,18 @@
+use inter_struct::prelude::*;
+
+mod first {
+    pub use super::second::IntoStruct;
+}
+
+mod second {
+    pub use super::first::IntoStruct;
+}
+
+/// This shouldn't compile, as the `use` declarations import each other.
+#[derive(StructInto)]
+#[struct_into("crate::first::IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use inter_struct::prelude::*;
+
+mod structs {
+    pub struct IntoStruct {
+        pub normal: String,
+    }
+}
+
+/// This shouldn't compile, as `IntoStruct` hasn't been imported into this module.
+#[derive(StructInto)]
+#[struct_into("IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use inter_struct::prelude::*;
+
+/// This shouldn't compile, as there's no module above the crate root.
+#[derive(StructInto)]
+#[struct_into("super::IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+pub struct IntoStruct {
+    pub normal: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use inter_struct::prelude::*;
+
+pub type IntoTuple = (String, u32);
+
+/// This shouldn't compile, as the type alias doesn't point to a struct.
+#[derive(StructInto)]
+#[struct_into("crate::IntoTuple")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use inter_struct::prelude::*;
+
+pub struct IntoStruct {
+    pub normal: String,
+}
+
+/// Paths with a leading `::` always point to another crate.
+#[derive(StructInto)]
+#[struct_into("::unknown_crate::IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use inter_struct::prelude::*;
+
+/// This shouldn't compile, as there's no struct with that name.
+#[derive(StructInto)]
+#[struct_into(name = "IntoStruct")]
+pub struct FromStruct {
+    pub normal: String,
+}
+
+fn main() {}
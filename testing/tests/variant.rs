@@ -0,0 +1,24 @@
+//! The `variant` feature enables fields with `#[cfg(feature = "variant")]` attributes.
+//! Features can't be enabled for the tests of the package itself, which is why its unit tests
+//! are run a second time with the feature.
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn unit_tests_with_variant_feature() {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+    // A separate target directory, as the one of the outer build is locked.
+    let status = Command::new(cargo)
+        .args(["test", "--lib", "--features", "variant", "--manifest-path"])
+        .arg(manifest)
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("variant"),
+        )
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}